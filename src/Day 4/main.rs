@@ -1,192 +1,132 @@
-use std::fs::File;
-use std::io::Read;
-use std::ops::{Deref, DerefMut};
+mod removal;
+mod rules;
+mod visualize;
 
-struct Grid(Vec<Vec<u8>>);
+use std::{fs::read_to_string, path::PathBuf};
 
-impl std::fmt::Display for Grid {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for row in &self.0 {
-            writeln!(f, "{}", String::from_utf8_lossy(row))?;
-        }
-        Ok(())
-    }
-}
+use aoc::grid::{Coord, Grid};
+use removal::{RemovalWave, RemovalWaves, occupied_neighbors};
+use rules::{AccessRule, Boundary, Neighborhood};
 
-impl FromIterator<Vec<u8>> for Grid {
-    fn from_iter<T: IntoIterator<Item = Vec<u8>>>(iter: T) -> Self {
-        Grid(iter.into_iter().collect())
-    }
+fn total_accessed(grid: &Grid<u8>, rule: &AccessRule) -> usize {
+    let occupied: Grid<bool> = grid.map(|&cell: &u8| rule.is_occupied(cell));
+
+    occupied
+        .coords()
+        .filter(|&coord: &Coord| {
+            occupied[coord] && rule.is_accessible(occupied_neighbors(&occupied, rule, coord))
+        })
+        .count()
 }
 
-impl Deref for Grid {
-    type Target = Vec<Vec<u8>>;
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
+fn total_removed(waves: &[RemovalWave]) -> usize {
+    waves
+        .iter()
+        .map(|wave: &RemovalWave| wave.removed.len())
+        .sum()
 }
 
-impl DerefMut for Grid {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
-    }
+struct Options {
+    rule: AccessRule,
+    render_dir: Option<PathBuf>,
+    scale: usize,
+    ansi: bool,
+    show_waves: bool,
 }
 
-const DIRECTIONS: [(isize, isize); 8] = [
-    (1, 0),
-    (1, -1),
-    (0, -1),
-    (-1, -1),
-    (-1, 0),
-    (-1, 1),
-    (0, 1),
-    (1, 1),
-];
-
-fn total_accessed(lines: &Vec<String>) -> usize {
-    let mut count: usize = 0;
-    let height: usize = lines.len();
-    let width: usize = lines.get(0).expect("Lines must not be empty").len();
-
-    for (y, line) in lines.iter().enumerate() {
-        for (x, character) in line.chars().enumerate() {
-            if character != '@' {
+fn parse_options(mut args: impl Iterator<Item = String>) -> Options {
+    let mut neighborhood: Neighborhood = Neighborhood::Moore;
+    let mut threshold: usize = 4;
+    let mut occupied: u8 = b'@';
+    let mut boundary: Boundary = Boundary::Clipped;
+    let mut render_dir: Option<PathBuf> = None;
+    let mut scale: usize = 4;
+    let mut ansi: bool = false;
+    let mut show_waves: bool = false;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--ansi" => {
+                ansi = true;
                 continue;
             }
-
-            let mut neighbor_count: i32 = 0;
-
-            for &(dx, dy) in &DIRECTIONS {
-                let ny: isize = y as isize + dy;
-                let nx: isize = x as isize + dx;
-
-                if ny < 0 || ny >= height as isize || nx < 0 || nx >= width as isize {
-                    continue;
-                }
-
-                if lines[ny as usize].as_bytes()[nx as usize] as char == '@' {
-                    neighbor_count += 1;
-                }
-            }
-
-            if neighbor_count < 4 {
-                count += 1;
+            "--waves" => {
+                show_waves = true;
+                continue;
             }
+            _ => {}
         }
-    }
-
-    count
-}
-
-fn dfs_remove(grid: &mut Grid, x: isize, y: isize, height: usize, width: usize) -> usize {
-    let mut removed_count: usize = 0;
-    let mut neighbor_count: usize = 0;
-
-    for &(dx, dy) in &DIRECTIONS {
-        let ny: isize = y as isize + dy;
-        let nx: isize = x as isize + dx;
 
-        if ny < 0 || ny >= height as isize || nx < 0 || nx >= width as isize {
-            continue;
-        }
-
-        if grid[ny as usize][nx as usize] as char == '@' {
-            neighbor_count += 1;
+        let value: String = args
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {flag}"));
+
+        match flag.as_str() {
+            "--neighborhood" => neighborhood = value.parse().unwrap_or_else(|e| panic!("{e}")),
+            "--threshold" => threshold = value.parse().expect("Invalid threshold"),
+            "--occupied" => match value.as_bytes() {
+                &[byte] => occupied = byte,
+                _ => panic!("Occupied marker must be a single byte, got '{value}'"),
+            },
+            "--boundary" => boundary = value.parse().unwrap_or_else(|e| panic!("{e}")),
+            "--render" => render_dir = Some(PathBuf::from(value)),
+            "--scale" => scale = value.parse().expect("Invalid scale"),
+            other => panic!("Unknown option {other}"),
         }
     }
 
-    if neighbor_count < 4 {
-        grid[y as usize][x as usize] = b'.';
-        removed_count += 1;
-        for &(dx, dy) in &DIRECTIONS {
-            if x + dx < 0
-                || x + dx >= width as isize
-                || y + dy < 0
-                || y + dy >= height as isize
-                || grid[(y + dy) as usize][(x + dx) as usize] != b'@'
-            {
-                continue;
-            }
-
-            removed_count += dfs_remove(grid, x + dx, y + dy, height, width);
-        }
+    Options {
+        rule: AccessRule::new(neighborhood)
+            .threshold(threshold)
+            .occupied(occupied)
+            .boundary(boundary),
+        render_dir,
+        scale,
+        ansi,
+        show_waves,
     }
-
-    removed_count
 }
 
-fn total_removed(lines: &Vec<String>) -> usize {
-    let mut count: usize = 0;
-    let height: usize = lines.len();
-    let width: usize = lines.get(0).expect("Lines must not be empty").len();
-
-    let mut grid: Grid = lines
-        .iter()
-        .map(|line: &String| line.as_bytes().to_vec())
-        .collect();
-
-    for y in 0..height {
-        for x in 0..width {
-            if grid[y][x] != b'@' {
-                continue;
-            }
+fn main() {
+    let options: Options = parse_options(std::env::args().skip(1));
+    let rule: &AccessRule = &options.rule;
 
-            let mut neighbor_count: i32 = 0;
+    let grid: Grid<u8> = match read_to_string("src/Day 4/input.txt") {
+        Ok(contents) => contents.parse().expect("Grid rows must have equal width"),
+        Err(_) => {
+            println!("Error reading the file.");
+            std::process::exit(1);
+        }
+    };
 
-            for &(dx, dy) in &DIRECTIONS {
-                let ny: isize = y as isize + dy;
-                let nx: isize = x as isize + dx;
+    if let Err(error) = rule.check_grid(&grid) {
+        println!("{error}");
+        std::process::exit(1);
+    }
 
-                if ny < 0 || ny >= height as isize || nx < 0 || nx >= width as isize {
-                    continue;
-                }
+    let waves: Vec<RemovalWave> = RemovalWaves::new(&grid, rule).collect();
 
-                if grid[ny as usize][nx as usize] as char == '@' {
-                    neighbor_count += 1;
-                }
-            }
+    println!("Part 1: {}", total_accessed(&grid, rule));
+    println!("Part 2: {}", total_removed(&waves));
 
-            if neighbor_count < 4 {
-                grid[y][x] = b'.';
-                count += 1;
-
-                for &(dx, dy) in &DIRECTIONS {
-                    let ny: isize = y as isize + dy;
-                    let nx: isize = x as isize + dx;
-                    if nx < 0
-                        || nx >= width as isize
-                        || ny < 0
-                        || ny >= height as isize
-                        || grid[ny as usize][nx as usize] != b'@'
-                    {
-                        continue;
-                    }
-
-                    count += dfs_remove(&mut grid, nx, ny, height, width);
-                }
-            }
+    if options.show_waves {
+        for wave in &waves {
+            println!(
+                "Round {}: removed {}, {} remaining",
+                wave.round,
+                wave.removed.len(),
+                wave.remaining
+            );
         }
     }
 
-    count
-}
-
-fn main() {
-    let lines: Vec<String> = match File::open("src/Day 4/input.txt") {
-        Ok(mut file) => {
-            let mut contents: String = String::new();
-            file.read_to_string(&mut contents).unwrap();
-            contents
-                .lines()
-                .map(|line: &str| line.to_string())
-                .collect()
-        }
-        Err(_) => {
-            println!("Error reading the file.");
-            std::process::exit(1);
-        }
-    };
+    if options.ansi {
+        visualize::print_ansi(&grid, rule, &waves).expect("Failed to print grid");
+    }
 
-    println!("Part 1: {}", total_accessed(&lines));
-    println!("Part 2: {}", total_removed(&lines));
+    if let Some(dir) = &options.render_dir {
+        visualize::write_frames(dir, &grid, rule, &waves, options.scale)
+            .expect("Failed to write images");
+        println!("Wrote {} frames to {}", waves.len() + 2, dir.display());
+    }
 }
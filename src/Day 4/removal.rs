@@ -0,0 +1,102 @@
+use aoc::grid::{Coord, Grid};
+
+use crate::rules::AccessRule;
+
+pub(crate) fn occupied_neighbors(occupied: &Grid<bool>, rule: &AccessRule, coord: Coord) -> usize {
+    rule.neighbors(occupied, coord)
+        .filter(|&neighbor: &Coord| occupied[neighbor])
+        .count()
+}
+
+/// The rolls removed together in one forklift pass.
+pub struct RemovalWave {
+    pub round: usize,
+    pub removed: Vec<Coord>,
+    pub remaining: usize,
+}
+
+/// Yields removal waves until no accessible roll is left.
+///
+/// Every roll in a wave is accessible once all earlier waves have been
+/// removed, so the number of waves is the number of passes needed.
+pub struct RemovalWaves {
+    rule: AccessRule,
+    occupied: Grid<bool>,
+    neighbor_counts: Grid<usize>,
+    pending: Vec<Coord>,
+    remaining: usize,
+    round: usize,
+}
+
+impl RemovalWaves {
+    pub(crate) fn new(grid: &Grid<u8>, rule: &AccessRule) -> Self {
+        let mut occupied: Grid<bool> = grid.map(|&cell: &u8| rule.is_occupied(cell));
+        let mut neighbor_counts: Grid<usize> = grid.map(|_| 0);
+        let mut remaining: usize = 0;
+
+        for coord in occupied.coords() {
+            if occupied[coord] {
+                neighbor_counts[coord] = occupied_neighbors(&occupied, rule, coord);
+                remaining += 1;
+            }
+        }
+
+        let pending: Vec<Coord> = occupied
+            .coords()
+            .filter(|&coord: &Coord| occupied[coord] && rule.is_accessible(neighbor_counts[coord]))
+            .collect();
+
+        // Clear on enqueue so a cell is never queued twice.
+        for &coord in &pending {
+            occupied[coord] = false;
+        }
+
+        Self {
+            rule: rule.clone(),
+            occupied,
+            neighbor_counts,
+            pending,
+            remaining,
+            round: 0,
+        }
+    }
+}
+
+impl Iterator for RemovalWaves {
+    type Item = RemovalWave;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pending.is_empty() {
+            return None;
+        }
+
+        let removed: Vec<Coord> = std::mem::take(&mut self.pending);
+        self.round += 1;
+        self.remaining -= removed.len();
+
+        let mut neighbors: Vec<Coord> = Vec::new();
+
+        for &coord in &removed {
+            neighbors.clear();
+            neighbors.extend(self.rule.neighbors(&self.occupied, coord));
+
+            for &neighbor in &neighbors {
+                if !self.occupied[neighbor] {
+                    continue;
+                }
+
+                self.neighbor_counts[neighbor] -= 1;
+                if self.rule.is_accessible(self.neighbor_counts[neighbor]) {
+                    self.occupied[neighbor] = false;
+                    self.pending.push(neighbor);
+                }
+            }
+        }
+
+        Some(RemovalWave {
+            round: self.round,
+            removed,
+            remaining: self.remaining,
+        })
+    }
+}
@@ -0,0 +1,181 @@
+use std::{fmt, str::FromStr};
+
+use aoc::grid::{ALL_DIRECTIONS, Coord, Grid, ORTHOGONAL};
+
+// Hex cells use "odd-r" offset coordinates: odd rows are shifted half a cell right.
+const HEX_EVEN_ROW: [(isize, isize); 6] = [(1, 0), (0, -1), (-1, -1), (-1, 0), (-1, 1), (0, 1)];
+const HEX_ODD_ROW: [(isize, isize); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (0, 1), (1, 1)];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighborhood {
+    VonNeumann,
+    Moore,
+    /// Every cell within the given Chebyshev distance.
+    Radius(usize),
+    Hex,
+}
+
+impl Neighborhood {
+    /// Neighbor offsets of a cell in an even or odd row. Radius offsets grow
+    /// quadratically, so they are generated rather than stored.
+    fn offsets(self, odd_row: bool) -> impl Iterator<Item = (isize, isize)> {
+        let fixed: &'static [(isize, isize)] = match self {
+            Self::VonNeumann => &ORTHOGONAL,
+            Self::Moore => &ALL_DIRECTIONS,
+            Self::Radius(_) => &[],
+            Self::Hex if odd_row => &HEX_ODD_ROW,
+            Self::Hex => &HEX_EVEN_ROW,
+        };
+        let radius: Option<isize> = match self {
+            Self::Radius(radius) => Some(radius as isize),
+            _ => None,
+        };
+
+        let square = radius.into_iter().flat_map(|r: isize| {
+            (-r..=r).flat_map(move |dy: isize| (-r..=r).map(move |dx: isize| (dx, dy)))
+        });
+        fixed
+            .iter()
+            .copied()
+            .chain(square.filter(|&offset: &(isize, isize)| offset != (0, 0)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Boundary {
+    Clipped,
+    Toroidal,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseRuleError(String);
+
+impl fmt::Display for ParseRuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseRuleError {}
+
+impl FromStr for Neighborhood {
+    type Err = ParseRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "von-neumann" => Ok(Self::VonNeumann),
+            "moore" => Ok(Self::Moore),
+            "hex" => Ok(Self::Hex),
+            other => other
+                .strip_prefix("radius=")
+                .and_then(|radius: &str| radius.parse().ok())
+                .map(Self::Radius)
+                .ok_or_else(|| ParseRuleError(format!("Unknown neighborhood '{other}'"))),
+        }
+    }
+}
+
+impl FromStr for Boundary {
+    type Err = ParseRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "clipped" => Ok(Self::Clipped),
+            "toroidal" => Ok(Self::Toroidal),
+            other => Err(ParseRuleError(format!("Unknown boundary '{other}'"))),
+        }
+    }
+}
+
+/// Decides which rolls a forklift can reach: an occupied cell is accessible
+/// when fewer than `threshold` of its neighbors are occupied.
+#[derive(Clone, Debug)]
+pub struct AccessRule {
+    neighborhood: Neighborhood,
+    threshold: usize,
+    occupied: u8,
+    boundary: Boundary,
+}
+
+impl AccessRule {
+    pub(crate) fn new(neighborhood: Neighborhood) -> Self {
+        Self {
+            neighborhood,
+            threshold: 4,
+            occupied: b'@',
+            boundary: Boundary::Clipped,
+        }
+    }
+
+    pub(crate) const fn threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    pub(crate) const fn occupied(mut self, occupied: u8) -> Self {
+        self.occupied = occupied;
+        self
+    }
+
+    pub(crate) const fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    /// Checks that the rule can be applied to `grid`. Wrapping an odd number of
+    /// hex rows puts two rows of the same parity next to each other, which
+    /// makes the neighbor relation asymmetric. A radius beyond the grid only
+    /// revisits the same cells.
+    pub(crate) fn check_grid<T>(&self, grid: &Grid<T>) -> Result<(), ParseRuleError> {
+        let size: usize = grid.width().max(grid.height());
+        if let Neighborhood::Radius(radius) = self.neighborhood
+            && radius > size
+        {
+            return Err(ParseRuleError(format!(
+                "Radius {radius} is larger than the {}x{} grid",
+                grid.width(),
+                grid.height()
+            )));
+        }
+
+        if self.neighborhood == Neighborhood::Hex
+            && self.boundary == Boundary::Toroidal
+            && grid.height() % 2 == 1
+        {
+            return Err(ParseRuleError(format!(
+                "A toroidal hex grid needs an even number of rows, got {}",
+                grid.height()
+            )));
+        }
+        Ok(())
+    }
+
+    pub(crate) const fn is_occupied(&self, cell: u8) -> bool {
+        cell == self.occupied
+    }
+
+    pub(crate) const fn is_accessible(&self, occupied_neighbors: usize) -> bool {
+        occupied_neighbors < self.threshold
+    }
+
+    /// Neighbors of `coord` under this rule. With a toroidal boundary a
+    /// neighbor may repeat on grids smaller than the neighborhood.
+    pub(crate) fn neighbors<'a, T>(
+        &'a self,
+        grid: &'a Grid<T>,
+        coord: Coord,
+    ) -> impl Iterator<Item = Coord> + 'a {
+        self.neighborhood
+            .offsets(coord.y % 2 == 1)
+            .filter_map(move |(dx, dy): (isize, isize)| match self.boundary {
+                Boundary::Clipped => grid.step(coord, dx, dy),
+                Boundary::Toroidal => grid.step_wrapping(coord, dx, dy),
+            })
+    }
+}
+
+impl Default for AccessRule {
+    fn default() -> Self {
+        Self::new(Neighborhood::Moore)
+    }
+}
@@ -0,0 +1,121 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use aoc::{
+    grid::{Coord, Grid},
+    render::{Rgb, heat, save_ppm, write_ansi},
+};
+
+use crate::{removal::RemovalWave, rules::AccessRule};
+
+const EMPTY: Rgb = [25, 25, 30];
+const ROLL: Rgb = [235, 235, 235];
+const CLEARED: Rgb = [70, 70, 95];
+const HIGHLIGHT: Rgb = [230, 60, 40];
+
+/// Round in which each cell was removed, or 0 if it never was.
+fn removal_rounds(grid: &Grid<u8>, waves: &[RemovalWave]) -> Grid<usize> {
+    let mut rounds: Grid<usize> = grid.map(|_| 0);
+    for wave in waves {
+        for &coord in &wave.removed {
+            rounds[coord] = wave.round;
+        }
+    }
+    rounds
+}
+
+fn round_color(round: usize, total_rounds: usize) -> Rgb {
+    heat((round - 1) as f64 / total_rounds.saturating_sub(1).max(1) as f64)
+}
+
+/// The warehouse right after `round` waves, with that round's removals highlighted.
+fn wave_frame(grid: &Grid<u8>, rule: &AccessRule, rounds: &Grid<usize>, round: usize) -> Grid<Rgb> {
+    let mut pixels: Grid<Rgb> = grid.map(|_| EMPTY);
+    for (coord, &cell) in grid.iter() {
+        if !rule.is_occupied(cell) {
+            continue;
+        }
+        pixels[coord] = match rounds[coord] {
+            0 => ROLL,
+            removed if removed > round => ROLL,
+            removed if removed == round => HIGHLIGHT,
+            _ => CLEARED,
+        };
+    }
+    pixels
+}
+
+/// The warehouse after all removals, each cleared cell colored by its round.
+fn heat_frame(grid: &Grid<u8>, rule: &AccessRule, rounds: &Grid<usize>, total: usize) -> Grid<Rgb> {
+    let mut pixels: Grid<Rgb> = grid.map(|_| EMPTY);
+    for (coord, &cell) in grid.iter() {
+        if rule.is_occupied(cell) {
+            pixels[coord] = match rounds[coord] {
+                0 => ROLL,
+                round => round_color(round, total),
+            };
+        }
+    }
+    pixels
+}
+
+/// Writes `before.ppm`, one `wave_NNN.ppm` per removal round and a
+/// round-colored `after.ppm` into `dir`.
+pub(crate) fn write_frames(
+    dir: &Path,
+    grid: &Grid<u8>,
+    rule: &AccessRule,
+    waves: &[RemovalWave],
+    scale: usize,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    let rounds: Grid<usize> = removal_rounds(grid, waves);
+
+    save_ppm(
+        &dir.join("before.ppm"),
+        &wave_frame(grid, rule, &rounds, 0),
+        scale,
+    )?;
+
+    for wave in waves {
+        save_ppm(
+            &dir.join(format!("wave_{:03}.ppm", wave.round)),
+            &wave_frame(grid, rule, &rounds, wave.round),
+            scale,
+        )?;
+    }
+
+    save_ppm(
+        &dir.join("after.ppm"),
+        &heat_frame(grid, rule, &rounds, waves.len()),
+        scale,
+    )
+}
+
+/// Prints the grid with removed rolls colored by round, early rounds in red
+/// and late rounds in blue.
+pub(crate) fn print_ansi(
+    grid: &Grid<u8>,
+    rule: &AccessRule,
+    waves: &[RemovalWave],
+) -> io::Result<()> {
+    let rounds: Grid<usize> = removal_rounds(grid, waves);
+    let mut colors: Grid<Option<Rgb>> = grid.map(|_| None);
+
+    for coord in grid
+        .coords()
+        .filter(|&coord: &Coord| rule.is_occupied(grid[coord]))
+    {
+        colors[coord] = Some(match rounds[coord] {
+            0 => ROLL,
+            round => round_color(round, waves.len()),
+        });
+    }
+
+    let mut out: BufWriter<io::StdoutLock<'_>> = BufWriter::new(io::stdout().lock());
+    write_ansi(&mut out, grid, &colors)?;
+    out.flush()
+}
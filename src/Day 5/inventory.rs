@@ -0,0 +1,95 @@
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    InvalidRange {
+        line: usize,
+        text: String,
+    },
+    InvertedRange {
+        line: usize,
+        start: usize,
+        end: usize,
+    },
+    InvalidIngredient {
+        line: usize,
+        text: String,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRange { line, text } => {
+                write!(f, "line {line}: expected a range like 3-5, got '{text}'")
+            }
+            Self::InvertedRange { line, start, end } => {
+                write!(f, "line {line}: range {start}-{end} ends before it starts")
+            }
+            Self::InvalidIngredient { line, text } => {
+                write!(f, "line {line}: expected an ingredient ID, got '{text}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The fresh ID ranges and the available ingredient IDs, in file order.
+///
+/// The two sections are separated by the first blank line, so input starting
+/// with a blank line has no ranges; the ingredient section may be missing
+/// entirely.
+pub struct Inventory {
+    pub ranges: Vec<(usize, usize)>,
+    pub ingredients: Vec<usize>,
+}
+
+pub(crate) fn parse_range(line: usize, text: &str) -> Result<(usize, usize), ParseError> {
+    let invalid = || ParseError::InvalidRange {
+        line,
+        text: text.to_string(),
+    };
+
+    let (start, end) = text.split_once('-').ok_or_else(invalid)?;
+    let start: usize = start.trim().parse().map_err(|_| invalid())?;
+    let end: usize = end.trim().parse().map_err(|_| invalid())?;
+
+    if end < start {
+        return Err(ParseError::InvertedRange { line, start, end });
+    }
+
+    Ok((start, end))
+}
+
+impl FromStr for Inventory {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(index, line): (usize, &str)| (index + 1, line.trim()));
+
+        let mut ranges: Vec<(usize, usize)> = Vec::new();
+        for (number, line) in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+            ranges.push(parse_range(number, line)?);
+        }
+
+        let mut ingredients: Vec<usize> = Vec::new();
+        for (number, line) in lines.filter(|&(_, line): &(usize, &str)| !line.is_empty()) {
+            ingredients.push(line.parse().map_err(|_| ParseError::InvalidIngredient {
+                line: number,
+                text: line.to_string(),
+            })?);
+        }
+
+        Ok(Self {
+            ranges,
+            ingredients,
+        })
+    }
+}
@@ -1,26 +1,61 @@
-use std::{fs::read_to_string, str::Split};
+mod inventory;
+mod report;
+mod session;
 
-fn count_fresh_ingredients(
-    processed_ranges: &Vec<(usize, usize)>,
-    ingredients: &Vec<usize>,
-) -> usize {
-    ingredients
+use std::{
+    fs::{self, File, read_to_string},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use aoc::interval_set::IntervalSet;
+use inventory::{Inventory, ParseError};
+use report::FreshnessReport;
+
+fn count_fresh_ingredients(fresh: &IntervalSet<usize>, ingredients: &[usize]) -> usize {
+    fresh
+        .find_all(ingredients)
         .iter()
-        .filter(|&&ingredient| {
-            processed_ranges
-                .iter()
-                .any(|&(start, end)| ingredient >= start && ingredient <= end)
-        })
+        .filter(|range: &&Option<usize>| range.is_some())
         .count()
 }
 
-fn count_fresh_ranges(processed_ranges: &Vec<(usize, usize)>) -> usize {
-    processed_ranges
-        .iter()
-        .fold(0_usize, |acc, &(start, end)| acc + (end - start + 1))
+fn count_fresh_ranges(fresh: &IntervalSet<usize>) -> u128 {
+    fresh.len()
+}
+
+fn write_csv_files(dir: &Path, report: &FreshnessReport) -> std::io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let mut ingredients: BufWriter<File> =
+        BufWriter::new(File::create(dir.join("ingredients.csv"))?);
+    report.write_ingredients_csv(&mut ingredients)?;
+    ingredients.flush()?;
+
+    let mut gaps: BufWriter<File> = BufWriter::new(File::create(dir.join("gaps.csv"))?);
+    report.write_gaps_csv(&mut gaps)?;
+    gaps.flush()
 }
 
 fn main() {
+    let mut show_report: bool = false;
+    let mut csv_dir: Option<PathBuf> = None;
+    let mut interactive: bool = false;
+    let mut args = std::env::args().skip(1);
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--report" => show_report = true,
+            "--interactive" => interactive = true,
+            "--csv" => {
+                csv_dir = Some(PathBuf::from(
+                    args.next().expect("Missing directory for --csv"),
+                ))
+            }
+            other => panic!("Unknown option {other}"),
+        }
+    }
+
     let contents: String = match read_to_string("src/Day 5/input.txt") {
         Ok(c) => c,
         Err(e) => {
@@ -28,64 +63,44 @@ fn main() {
         }
     };
 
-    let mut sections: Split<'_, &str> = contents.split("\n\n");
-
-    let mut fresh_ranges: Vec<(usize, usize)> = Vec::new();
-    let mut ingredients: Vec<usize> = Vec::new();
-
-    let range_section: &str = sections.next().expect("No range section");
-    let ingredients_section: &str = sections.next().expect("No ingredient section");
-
-    for line in range_section.lines() {
-        if let Some((start, end)) = line.split_once('-') {
-            fresh_ranges.push((
-                start.trim().parse().expect("Invalid start number"),
-                end.trim().parse().expect("Invalid end number"),
-            ));
-        } else {
-            panic!("Invalid range line: {}", line);
+    let parsed: Result<Inventory, ParseError> = contents.parse();
+    let inventory: Inventory = match parsed {
+        Ok(inventory) => inventory,
+        Err(error) => {
+            println!("Invalid input: {error}");
+            std::process::exit(1);
         }
-    }
+    };
 
-    for line in ingredients_section.lines() {
-        ingredients.push(
-            line.trim()
-                .parse()
-                .expect(&format!("Invalid ingredient number {}", line)),
-        );
-    }
+    let mut fresh: IntervalSet<usize> = inventory.ranges.iter().copied().collect();
 
-    // Preprocess
-    fresh_ranges.sort_by(|a, b| a.0.cmp(&b.0));
-
-    let mut processed_ranges: Vec<(usize, usize)> = vec![(fresh_ranges[0])]; // Initialize with the first range
-
-    // Merge overlapping ranges
-    for i in 1..fresh_ranges.len() {
-        if fresh_ranges[i].0
-            <= processed_ranges
-                .last()
-                .expect("There's no last element in processed ranges")
-                .1
-                + 1
-        {
-            let (old_start, old_end) = processed_ranges
-                .pop()
-                .expect("There's no last element in processed ranges");
-
-            processed_ranges.push((old_start, fresh_ranges[i].1.max(old_end)));
-        } else {
-            processed_ranges.push(fresh_ranges[i]);
-        }
+    if interactive {
+        let stdout: io::Stdout = io::stdout();
+        session::run(&mut fresh, io::stdin().lock(), &mut stdout.lock())
+            .expect("Failed to run interactive session");
+        return;
     }
 
     println!(
         "Number of fresh ingredients: {}",
-        count_fresh_ingredients(&processed_ranges, &ingredients)
+        count_fresh_ingredients(&fresh, &inventory.ingredients)
     );
 
     println!(
         "Total number of fresh ingredient IDs in ranges: {}",
-        count_fresh_ranges(&processed_ranges)
+        count_fresh_ranges(&fresh)
     );
+
+    if show_report || csv_dir.is_some() {
+        let report: FreshnessReport = FreshnessReport::new(&inventory, &fresh);
+
+        if show_report {
+            report.print();
+        }
+
+        if let Some(dir) = &csv_dir {
+            write_csv_files(dir, &report).expect("Failed to write CSV files");
+            println!("Wrote ingredients.csv and gaps.csv to {}", dir.display());
+        }
+    }
 }
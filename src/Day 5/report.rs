@@ -0,0 +1,111 @@
+use std::io::{self, Write};
+
+use aoc::interval_set::IntervalSet;
+
+use crate::inventory::Inventory;
+
+pub struct IngredientStatus {
+    pub id: usize,
+    pub fresh: bool,
+    /// 1-based positions of the original ranges containing the ID.
+    pub range_ids: Vec<usize>,
+}
+
+/// Why each ingredient is fresh or spoiled, and which IDs no range covers.
+pub struct FreshnessReport {
+    pub ingredients: Vec<IngredientStatus>,
+    pub gaps: Vec<(usize, usize)>,
+}
+
+impl FreshnessReport {
+    pub(crate) fn new(inventory: &Inventory, fresh: &IntervalSet<usize>) -> Self {
+        // Original ranges grouped by the merged range that absorbed them.
+        let mut members: Vec<Vec<usize>> = vec![Vec::new(); fresh.ranges().len()];
+        for (index, &(start, _)) in inventory.ranges.iter().enumerate() {
+            let merged: usize = fresh.find(start).expect("Every range start is fresh");
+            members[merged].push(index);
+        }
+
+        let ingredients: Vec<IngredientStatus> = inventory
+            .ingredients
+            .iter()
+            .zip(fresh.find_all(&inventory.ingredients))
+            .map(|(&id, merged): (&usize, Option<usize>)| {
+                let range_ids: Vec<usize> = merged
+                    .map(|merged: usize| {
+                        members[merged]
+                            .iter()
+                            .filter(|&&index| {
+                                let (start, end) = inventory.ranges[index];
+                                start <= id && id <= end
+                            })
+                            .map(|&index| index + 1)
+                            .collect()
+                    })
+                    .unwrap_or_default();
+
+                IngredientStatus {
+                    id,
+                    fresh: merged.is_some(),
+                    range_ids,
+                }
+            })
+            .collect();
+
+        let gaps: Vec<(usize, usize)> = match (fresh.ranges().first(), fresh.ranges().last()) {
+            (Some(&(lo, _)), Some(&(_, hi))) => fresh.complement(lo, hi).ranges().to_vec(),
+            _ => Vec::new(),
+        };
+
+        Self { ingredients, gaps }
+    }
+
+    pub(crate) fn print(&self) {
+        for ingredient in &self.ingredients {
+            if ingredient.fresh {
+                println!(
+                    "{}: fresh (ranges {})",
+                    ingredient.id,
+                    join_ids(&ingredient.range_ids, ", ")
+                );
+            } else {
+                println!("{}: spoiled", ingredient.id);
+            }
+        }
+
+        for &(start, end) in &self.gaps {
+            println!("Gap: {start}-{end} ({} IDs)", end - start + 1);
+        }
+    }
+
+    /// One row per ingredient: `id,status,ranges` with range IDs separated by `;`.
+    pub(crate) fn write_ingredients_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "id,status,ranges")?;
+        for ingredient in &self.ingredients {
+            writeln!(
+                out,
+                "{},{},{}",
+                ingredient.id,
+                if ingredient.fresh { "fresh" } else { "spoiled" },
+                join_ids(&ingredient.range_ids, ";")
+            )?;
+        }
+        Ok(())
+    }
+
+    /// One row per uncovered gap: `start,end,length`.
+    pub(crate) fn write_gaps_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "start,end,length")?;
+        for &(start, end) in &self.gaps {
+            writeln!(out, "{start},{end},{}", end - start + 1)?;
+        }
+        Ok(())
+    }
+}
+
+fn join_ids(ids: &[usize], separator: &str) -> String {
+    ids.iter()
+        .map(usize::to_string)
+        .collect::<Vec<String>>()
+        .join(separator)
+}
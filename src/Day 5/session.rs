@@ -0,0 +1,93 @@
+use std::io::{self, BufRead, Write};
+
+use aoc::interval_set::IntervalSet;
+
+use crate::inventory::parse_range;
+
+enum Command {
+    Add(usize, usize),
+    Remove(usize, usize),
+    Query(usize),
+    Count,
+    Ranges,
+    Quit,
+}
+
+fn parse_command(line_number: usize, line: &str) -> Result<Command, String> {
+    let (name, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument: &str = argument.trim();
+
+    match name {
+        "add" => parse_range(line_number, argument)
+            .map(|(start, end)| Command::Add(start, end))
+            .map_err(|e| e.to_string()),
+        "remove" => parse_range(line_number, argument)
+            .map(|(start, end)| Command::Remove(start, end))
+            .map_err(|e| e.to_string()),
+        "query" => argument.parse().map(Command::Query).map_err(|_| {
+            format!("line {line_number}: expected an ingredient ID, got '{argument}'")
+        }),
+        "count" => Ok(Command::Count),
+        "ranges" => Ok(Command::Ranges),
+        "quit" => Ok(Command::Quit),
+        other => Err(format!("line {line_number}: unknown command '{other}'")),
+    }
+}
+
+/// Answers one command per input line against a live set of fresh ranges:
+///
+/// - `add A-B` / `remove A-B` update the set and reply `ok`
+/// - `query ID` replies `fresh` or `spoiled`
+/// - `count` replies with the number of fresh IDs
+/// - `ranges` lists the merged ranges, followed by `end`
+/// - `quit` stops reading
+///
+/// Malformed commands reply `error: ...` and leave the set unchanged.
+pub(crate) fn run<R: BufRead, W: Write>(
+    fresh: &mut IntervalSet<usize>,
+    input: R,
+    out: &mut W,
+) -> io::Result<()> {
+    for (index, line) in input.lines().enumerate() {
+        let line: String = line?;
+        let line: &str = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        match parse_command(index + 1, line) {
+            Ok(Command::Add(start, end)) => {
+                fresh.insert(start, end);
+                writeln!(out, "ok")?;
+            }
+            Ok(Command::Remove(start, end)) => {
+                fresh.remove(start, end);
+                writeln!(out, "ok")?;
+            }
+            Ok(Command::Query(id)) => {
+                writeln!(
+                    out,
+                    "{}",
+                    if fresh.contains(id) {
+                        "fresh"
+                    } else {
+                        "spoiled"
+                    }
+                )?;
+            }
+            Ok(Command::Count) => writeln!(out, "{}", fresh.len())?,
+            Ok(Command::Ranges) => {
+                for &(start, end) in fresh.ranges() {
+                    writeln!(out, "{start}-{end}")?;
+                }
+                writeln!(out, "end")?;
+            }
+            Ok(Command::Quit) => break,
+            Err(message) => writeln!(out, "error: {message}")?,
+        }
+
+        out.flush()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,333 @@
+use std::{fmt, str::FromStr};
+
+use aoc::bigint::BigInt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Min,
+    Max,
+}
+
+impl FromStr for Op {
+    type Err = ExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "+" => Ok(Self::Add),
+            "-" => Ok(Self::Sub),
+            "*" => Ok(Self::Mul),
+            "/" => Ok(Self::Div),
+            "%" => Ok(Self::Rem),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            other => Err(ExprError::UnknownOperator(other.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbol: &str = match self {
+            Self::Add => "+",
+            Self::Sub => "-",
+            Self::Mul => "*",
+            Self::Div => "/",
+            Self::Rem => "%",
+            Self::Min => "min",
+            Self::Max => "max",
+        };
+        write!(f, "{symbol}")
+    }
+}
+
+/// A worksheet expression. Number literals keep their decimal digits so that
+/// evaluation decides how wide the arithmetic needs to be.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expr {
+    Number(String),
+    /// `op` folded left to right over the operands.
+    Apply(Op, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprError {
+    UnknownOperator(String),
+    Unexpected { text: String, position: usize },
+    Empty,
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownOperator(operator) => write!(f, "unknown operator '{operator}'"),
+            Self::Unexpected { text, position } => {
+                write!(f, "unexpected input at position {position} of '{text}'")
+            }
+            Self::Empty => write!(f, "empty expression"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalError {
+    Overflow,
+    DivisionByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overflow => write!(f, "arithmetic overflow"),
+            Self::DivisionByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+impl std::error::Error for EvalError {}
+
+/// A number type worksheets can be evaluated in.
+pub trait Value: Sized + Clone + Ord + fmt::Display {
+    fn zero() -> Self;
+    fn from_digits(digits: &str) -> Result<Self, EvalError>;
+    fn apply(op: Op, lhs: Self, rhs: Self) -> Result<Self, EvalError>;
+}
+
+impl Value for i128 {
+    fn zero() -> Self {
+        0
+    }
+
+    fn from_digits(digits: &str) -> Result<Self, EvalError> {
+        digits.parse().map_err(|_| EvalError::Overflow)
+    }
+
+    fn apply(op: Op, lhs: Self, rhs: Self) -> Result<Self, EvalError> {
+        let result: Option<i128> = match op {
+            Op::Add => lhs.checked_add(rhs),
+            Op::Sub => lhs.checked_sub(rhs),
+            Op::Mul => lhs.checked_mul(rhs),
+            Op::Div | Op::Rem if rhs == 0 => return Err(EvalError::DivisionByZero),
+            Op::Div => lhs.checked_div(rhs),
+            Op::Rem => lhs.checked_rem(rhs),
+            Op::Min => Some(lhs.min(rhs)),
+            Op::Max => Some(lhs.max(rhs)),
+        };
+        result.ok_or(EvalError::Overflow)
+    }
+}
+
+impl Value for BigInt {
+    fn zero() -> Self {
+        Self::zero()
+    }
+
+    fn from_digits(digits: &str) -> Result<Self, EvalError> {
+        Self::from_decimal(digits).ok_or(EvalError::Overflow)
+    }
+
+    fn apply(op: Op, lhs: Self, rhs: Self) -> Result<Self, EvalError> {
+        Ok(match op {
+            Op::Add => &lhs + &rhs,
+            Op::Sub => &lhs - &rhs,
+            Op::Mul => &lhs * &rhs,
+            Op::Div => lhs.div_rem(&rhs).ok_or(EvalError::DivisionByZero)?.0,
+            Op::Rem => lhs.div_rem(&rhs).ok_or(EvalError::DivisionByZero)?.1,
+            Op::Min => lhs.min(rhs),
+            Op::Max => lhs.max(rhs),
+        })
+    }
+}
+
+impl Expr {
+    pub(crate) fn evaluate<V: Value>(&self) -> Result<V, EvalError> {
+        match self {
+            Self::Number(digits) => V::from_digits(digits),
+            Self::Apply(op, operands) => {
+                let mut values = operands.iter().map(Self::evaluate::<V>);
+                let first: V = values.next().unwrap_or_else(|| Ok(V::zero()))?;
+                values.try_fold(first, |acc: V, value: Result<V, EvalError>| {
+                    V::apply(*op, acc, value?)
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(digits) => write!(f, "{digits}"),
+            Self::Apply(op @ (Op::Min | Op::Max), operands) => {
+                write!(f, "{op}(")?;
+                for (i, operand) in operands.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{operand}")?;
+                }
+                write!(f, ")")
+            }
+            Self::Apply(op, operands) => {
+                write!(f, "(")?;
+                for (i, operand) in operands.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " {op} ")?;
+                    }
+                    write!(f, "{operand}")?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Recursive-descent parser for a single operand cell:
+///
+/// ```text
+/// expr   := term (('+' | '-') term)*
+/// term   := factor (('*' | '/' | '%') factor)*
+/// factor := digits | '(' expr ')' | ('min' | 'max') '(' expr (',' expr)* ')'
+/// ```
+struct Parser<'a> {
+    text: &'a str,
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    const fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            bytes: text.as_bytes(),
+            position: 0,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.position += 1;
+        }
+    }
+
+    fn unexpected(&self) -> ExprError {
+        ExprError::Unexpected {
+            text: self.text.to_string(),
+            position: self.position,
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.bytes.get(self.position).copied()
+    }
+
+    fn eat(&mut self, expected: &[u8]) -> bool {
+        self.skip_whitespace();
+        if self.bytes[self.position..].starts_with(expected) {
+            self.position += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), ExprError> {
+        if self.eat(&[expected]) {
+            Ok(())
+        } else {
+            Err(self.unexpected())
+        }
+    }
+
+    fn binary(
+        &mut self,
+        ops: &[(u8, Op)],
+        next: fn(&mut Self) -> Result<Expr, ExprError>,
+    ) -> Result<Expr, ExprError> {
+        let mut lhs: Expr = next(self)?;
+
+        while let Some(&(_, op)) = ops.iter().find(|&&(symbol, _)| self.peek() == Some(symbol)) {
+            self.position += 1;
+            let rhs: Expr = next(self)?;
+            lhs = Expr::Apply(op, vec![lhs, rhs]);
+        }
+
+        Ok(lhs)
+    }
+
+    fn expr(&mut self) -> Result<Expr, ExprError> {
+        self.binary(&[(b'+', Op::Add), (b'-', Op::Sub)], Self::term)
+    }
+
+    fn term(&mut self) -> Result<Expr, ExprError> {
+        self.binary(
+            &[(b'*', Op::Mul), (b'/', Op::Div), (b'%', Op::Rem)],
+            Self::factor,
+        )
+    }
+
+    fn factor(&mut self) -> Result<Expr, ExprError> {
+        if self.eat(b"(") {
+            let inner: Expr = self.expr()?;
+            self.expect(b')')?;
+            return Ok(inner);
+        }
+
+        for (name, op) in [(&b"min"[..], Op::Min), (&b"max"[..], Op::Max)] {
+            if self.eat(name) {
+                self.expect(b'(')?;
+                let mut operands: Vec<Expr> = vec![self.expr()?];
+                while self.eat(b",") {
+                    operands.push(self.expr()?);
+                }
+                self.expect(b')')?;
+                return Ok(Expr::Apply(op, operands));
+            }
+        }
+
+        self.skip_whitespace();
+        let start: usize = self.position;
+        while self
+            .bytes
+            .get(self.position)
+            .is_some_and(u8::is_ascii_digit)
+        {
+            self.position += 1;
+        }
+
+        if start == self.position {
+            return Err(self.unexpected());
+        }
+
+        Ok(Expr::Number(self.text[start..self.position].to_string()))
+    }
+}
+
+impl FromStr for Expr {
+    type Err = ExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser: Parser<'_> = Parser::new(s);
+        if parser.peek().is_none() {
+            return Err(ExprError::Empty);
+        }
+
+        let expr: Expr = parser.expr()?;
+        if parser.peek().is_some() {
+            return Err(parser.unexpected());
+        }
+
+        Ok(expr)
+    }
+}
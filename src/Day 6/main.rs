@@ -1,103 +1,132 @@
+mod expr;
+mod worksheet;
+
 use std::{
     fs::File,
     io::{BufRead, BufReader},
 };
 
-fn evaluate(inputs: &[String]) -> usize {
-    let processed_inputs: Vec<Vec<&str>> = inputs
-        .iter()
-        .map(|line: &String| line.split_whitespace().collect::<Vec<&str>>())
-        .collect::<Vec<Vec<&str>>>();
-
-    let height: usize = processed_inputs.len();
-    let width: usize = processed_inputs[0].len();
-    let ops_row: &Vec<&str> = processed_inputs.last().expect("No operator row found");
-
-    (0..width)
-        .map(|column| {
-            let numbers_iter = (0..height - 1).map(|row: usize| {
-                processed_inputs[row][column]
-                    .parse::<usize>()
-                    .expect("Failed to parse a number")
-            });
-
-            match ops_row[column] {
-                "+" => numbers_iter.sum::<usize>(),
-                "*" => numbers_iter.product::<usize>(),
-                operator => panic!("Unknown operator: {}", operator),
-            }
-        })
-        .sum::<usize>()
+use aoc::bigint::BigInt;
+use expr::{EvalError, Expr, Op, Value};
+use worksheet::{Layout, Problem, Worksheet, WorksheetError};
+
+fn sum_problems<V: Value>(problems: &[Problem]) -> Result<V, WorksheetError> {
+    let mut total: V = V::zero();
+
+    for (index, problem) in problems.iter().enumerate() {
+        let eval_error = |error: EvalError| WorksheetError::Eval {
+            problem: index + 1,
+            columns: problem.columns.clone(),
+            error,
+        };
+        let value: V = problem.expr.evaluate().map_err(eval_error)?;
+        total = V::apply(Op::Add, total, value).map_err(eval_error)?;
+    }
+
+    Ok(total)
 }
 
-fn column_wise_evaluate(inputs: &[String]) -> usize {
-    let (rows, op_row) = inputs.split_at(inputs.len() - 1);
-    let op_line: &String = &op_row[0];
+fn evaluate<V: Value>(worksheet: &Worksheet) -> Result<V, WorksheetError> {
+    sum_problems(&worksheet.row_wise_problems()?)
+}
 
-    let operators: Vec<&str> = op_line.split_whitespace().collect();
+fn column_wise_evaluate<V: Value>(worksheet: &Worksheet) -> Result<V, WorksheetError> {
+    sum_problems(&worksheet.column_wise_problems()?)
+}
 
-    let height = rows.len();
-    let width = rows[0].len();
+fn report<V: Value>(label: &str, result: Result<V, WorksheetError>) {
+    match result {
+        Ok(result) => println!("{label}: {result}"),
+        Err(
+            error @ WorksheetError::Eval {
+                error: EvalError::Overflow,
+                ..
+            },
+        ) => println!("{label} failed: {error} (rerun with --exact)"),
+        Err(error) => println!("{label} failed: {error}"),
+    }
+}
 
-    let mut sum: usize = 0;
-    let mut op_idx: usize = 0;
+fn describe<V: Value>(op: Op, operands: &[Expr]) -> String {
+    let expr: Expr = Expr::Apply(op, operands.to_vec());
+    match expr.evaluate::<V>() {
+        Ok(value) => format!("{expr} = {value}"),
+        Err(error) => format!("{expr} = error: {error}"),
+    }
+}
 
-    fn init_semi_total(op: &str) -> usize {
-        match op {
-            "+" => 0,
-            "*" => 1,
-            other => panic!("Unknown operator: {other}"),
-        }
+fn print_breakdown<V: Value>(worksheet: &Worksheet) -> Result<(), WorksheetError> {
+    for (index, problem) in worksheet.breakdown()?.iter().enumerate() {
+        println!(
+            "Problem {} (columns {}-{}): {}",
+            index + 1,
+            problem.columns.start,
+            problem.columns.end - 1,
+            problem.operator
+        );
+        println!(
+            "  row-wise:    {}",
+            describe::<V>(problem.operator, &problem.row_operands)
+        );
+        println!(
+            "  column-wise: {}",
+            describe::<V>(problem.operator, &problem.column_operands)
+        );
     }
+    Ok(())
+}
 
-    let mut semi_total = init_semi_total(operators[0]);
+fn main() {
+    let mut exact: bool = false;
+    let mut breakdown: bool = false;
+    let mut layout: Layout = Layout::default();
+
+    for flag in std::env::args().skip(1) {
+        match flag.as_str() {
+            "--exact" => exact = true,
+            "--breakdown" => breakdown = true,
+            "--right-to-left" => layout.right_to_left = true,
+            "--bottom-to-top" => layout.bottom_to_top = true,
+            "--operators-on-top" => layout.operators_on_top = true,
+            other => panic!("Unknown option {other}"),
+        }
+    }
 
-    for x in 0..width {
-        let mut has_digit: bool = false;
-        let mut number: usize = 0;
+    let file: File = File::open("src/Day 6/input.txt").expect("Failed to open file");
 
-        for y in 0..height {
-            let b: u8 = rows[y].as_bytes()[x];
-            let c: char = b as char;
+    let lines: Vec<String> = BufReader::new(file)
+        .lines()
+        .map(|line: Result<String, std::io::Error>| line.expect("Failed to read line"))
+        .collect::<Vec<String>>();
 
-            if c.is_ascii_digit() {
-                has_digit = true;
-                number = number * 10 + (b - b'0') as usize;
-            }
+    let worksheet: Worksheet = match Worksheet::parse(&lines, layout) {
+        Ok(worksheet) => worksheet,
+        Err(error) => {
+            println!("Invalid worksheet: {error}");
+            std::process::exit(1);
         }
+    };
 
-        if has_digit {
-            match operators[op_idx] {
-                "+" => semi_total += number,
-                "*" => semi_total *= number,
-                other => panic!("Unknown operator: {other}"),
-            }
+    if breakdown {
+        let printed: Result<(), WorksheetError> = if exact {
+            print_breakdown::<BigInt>(&worksheet)
         } else {
-            sum += semi_total;
-            op_idx += 1;
+            print_breakdown::<i128>(&worksheet)
+        };
 
-            if op_idx >= operators.len() {
-                panic!("Insufficient operators for the number of columns");
-            }
-
-            semi_total = init_semi_total(operators[op_idx]);
+        if let Err(error) = printed {
+            println!("Breakdown failed: {error}");
         }
     }
 
-    sum + semi_total
-}
+    let part1: &str = "Evaluated Result (Part 1)";
+    let part2: &str = "Column-wise Evaluated Result (Part 2)";
 
-fn main() {
-    let file: File = File::open("src/Day 6/input.txt").expect("Failed to open file");
-
-    let lines: Vec<String> = BufReader::new(file)
-        .lines()
-        .map(|line: Result<String, std::io::Error>| line.expect("Failed to read line"))
-        .collect::<Vec<String>>();
-
-    println!("Evaluated Result (Part 1): {}", evaluate(&lines));
-    println!(
-        "Column-wise Evaluated Result (Part 2): {}",
-        column_wise_evaluate(&lines)
-    );
+    if exact {
+        report(part1, evaluate::<BigInt>(&worksheet));
+        report(part2, column_wise_evaluate::<BigInt>(&worksheet));
+    } else {
+        report(part1, evaluate::<i128>(&worksheet));
+        report(part2, column_wise_evaluate::<i128>(&worksheet));
+    }
 }
@@ -0,0 +1,273 @@
+use std::{fmt, ops::Range};
+
+use aoc::grid::{Coord, Grid};
+
+use crate::expr::{EvalError, Expr, ExprError, Op};
+
+#[derive(Debug)]
+pub enum WorksheetError {
+    MissingOperatorRow,
+    Malformed {
+        columns: Range<usize>,
+        reason: String,
+    },
+    Eval {
+        problem: usize,
+        columns: Range<usize>,
+        error: EvalError,
+    },
+}
+
+impl fmt::Display for WorksheetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingOperatorRow => write!(f, "worksheet has no operator row"),
+            Self::Malformed { columns, reason } => {
+                write!(f, "columns {}-{}: {reason}", columns.start, columns.end - 1)
+            }
+            Self::Eval {
+                problem,
+                columns,
+                error,
+            } => write!(
+                f,
+                "problem {problem} (columns {}-{}): {error}",
+                columns.start,
+                columns.end - 1
+            ),
+        }
+    }
+}
+
+impl std::error::Error for WorksheetError {}
+
+/// One problem of the worksheet and the columns it occupies.
+pub struct Problem {
+    pub columns: Range<usize>,
+    pub expr: Expr,
+}
+
+/// Everything read for one problem, in both reading orders.
+pub struct ProblemBreakdown {
+    pub columns: Range<usize>,
+    pub operator: Op,
+    pub row_operands: Vec<Expr>,
+    pub column_operands: Vec<Expr>,
+}
+
+/// How a worksheet is laid out on the page.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Layout {
+    /// Read problems, and the columns within a problem, from right to left.
+    pub right_to_left: bool,
+    /// Read the digits of a column from bottom to top.
+    pub bottom_to_top: bool,
+    /// The operator row is the first line instead of the last.
+    pub operators_on_top: bool,
+}
+
+/// A worksheet laid out as a character grid of number rows and one operator
+/// row. Lines shorter than the longest one are padded with spaces, so
+/// trailing whitespace trimmed by an editor does not matter.
+pub struct Worksheet {
+    grid: Grid<u8>,
+    layout: Layout,
+}
+
+impl Worksheet {
+    pub(crate) fn parse(lines: &[String], layout: Layout) -> Result<Self, WorksheetError> {
+        let is_content = |line: &String| !line.trim().is_empty();
+        let first: usize = lines
+            .iter()
+            .position(is_content)
+            .ok_or(WorksheetError::MissingOperatorRow)?;
+        let last: usize = lines
+            .iter()
+            .rposition(is_content)
+            .ok_or(WorksheetError::MissingOperatorRow)?;
+
+        Ok(Self {
+            grid: Grid::from_rows_padded(
+                lines[first..=last].iter().map(|line: &String| line.bytes()),
+                b' ',
+            ),
+            layout,
+        })
+    }
+
+    const fn operator_row(&self) -> usize {
+        if self.layout.operators_on_top {
+            0
+        } else {
+            self.grid.height() - 1
+        }
+    }
+
+    fn number_rows(&self) -> Range<usize> {
+        if self.layout.operators_on_top {
+            1..self.grid.height()
+        } else {
+            0..self.grid.height() - 1
+        }
+    }
+
+    /// Number rows in the order their digits are read.
+    fn digit_rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = self.number_rows().collect();
+        if self.layout.bottom_to_top {
+            rows.reverse();
+        }
+        rows
+    }
+
+    fn in_reading_order<T>(&self, mut items: Vec<T>) -> Vec<T> {
+        if self.layout.right_to_left {
+            items.reverse();
+        }
+        items
+    }
+
+    fn cell(&self, row: usize, columns: &Range<usize>) -> &str {
+        std::str::from_utf8(&self.grid.row(row)[columns.clone()])
+            .expect("Worksheet lines are valid UTF-8")
+            .trim()
+    }
+
+    /// Column ranges of the problems in reading order. Problems are separated by
+    /// columns that are blank in every row, including the operator row.
+    pub(crate) fn spans(&self) -> Vec<Range<usize>> {
+        let mut spans: Vec<Range<usize>> = Vec::new();
+        let mut start: Option<usize> = None;
+
+        for x in 0..=self.grid.width() {
+            let blank: bool =
+                x == self.grid.width() || self.grid.column(x).all(|b: &u8| b.is_ascii_whitespace());
+
+            match (blank, start) {
+                (false, None) => start = Some(x),
+                (true, Some(begin)) => {
+                    spans.push(begin..x);
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        self.in_reading_order(spans)
+    }
+
+    fn malformed(columns: &Range<usize>, reason: String) -> WorksheetError {
+        WorksheetError::Malformed {
+            columns: columns.clone(),
+            reason,
+        }
+    }
+
+    pub(crate) fn operator(&self, columns: &Range<usize>) -> Result<Op, WorksheetError> {
+        let cell: &str = self.cell(self.operator_row(), columns);
+        if cell.is_empty() {
+            return Err(Self::malformed(columns, "missing operator".to_string()));
+        }
+        cell.parse()
+            .map_err(|error: ExprError| Self::malformed(columns, error.to_string()))
+    }
+
+    /// One operand per number row, each read as a (possibly parenthesized) expression.
+    pub(crate) fn row_operands(&self, columns: &Range<usize>) -> Result<Vec<Expr>, WorksheetError> {
+        if self.number_rows().is_empty() {
+            return Err(Self::malformed(columns, "no operands".to_string()));
+        }
+
+        self.number_rows()
+            .map(|row: usize| {
+                let cell: &str = self.cell(row, columns);
+                if cell.is_empty() {
+                    return Err(Self::malformed(
+                        columns,
+                        format!("row {} has no operand", row + 1),
+                    ));
+                }
+                cell.parse().map_err(|error: ExprError| {
+                    Self::malformed(columns, format!("row {}: {error}", row + 1))
+                })
+            })
+            .collect()
+    }
+
+    /// One operand per column in reading order, its digits read in the layout's digit order.
+    pub(crate) fn column_operands(
+        &self,
+        columns: &Range<usize>,
+    ) -> Result<Vec<Expr>, WorksheetError> {
+        let mut operands: Vec<Expr> = Vec::new();
+
+        let digit_rows: Vec<usize> = self.digit_rows();
+
+        for x in self.in_reading_order(columns.clone().collect()) {
+            let mut digits: String = String::new();
+
+            for &row in &digit_rows {
+                match self.grid[Coord::new(x, row)] {
+                    b if b.is_ascii_digit() => digits.push(b as char),
+                    b if b.is_ascii_whitespace() => {}
+                    other => {
+                        return Err(Self::malformed(
+                            columns,
+                            format!(
+                                "unexpected '{}' at row {}, column {x}",
+                                other as char,
+                                row + 1
+                            ),
+                        ));
+                    }
+                }
+            }
+
+            if !digits.is_empty() {
+                operands.push(Expr::Number(digits));
+            }
+        }
+
+        if operands.is_empty() {
+            return Err(Self::malformed(columns, "no operands".to_string()));
+        }
+
+        Ok(operands)
+    }
+
+    pub(crate) fn row_wise_problems(&self) -> Result<Vec<Problem>, WorksheetError> {
+        self.spans()
+            .into_iter()
+            .map(|columns: Range<usize>| {
+                let expr: Expr =
+                    Expr::Apply(self.operator(&columns)?, self.row_operands(&columns)?);
+                Ok(Problem { columns, expr })
+            })
+            .collect()
+    }
+
+    pub(crate) fn column_wise_problems(&self) -> Result<Vec<Problem>, WorksheetError> {
+        self.spans()
+            .into_iter()
+            .map(|columns: Range<usize>| {
+                let expr: Expr =
+                    Expr::Apply(self.operator(&columns)?, self.column_operands(&columns)?);
+                Ok(Problem { columns, expr })
+            })
+            .collect()
+    }
+
+    pub(crate) fn breakdown(&self) -> Result<Vec<ProblemBreakdown>, WorksheetError> {
+        self.spans()
+            .into_iter()
+            .map(|columns: Range<usize>| {
+                Ok(ProblemBreakdown {
+                    operator: self.operator(&columns)?,
+                    row_operands: self.row_operands(&columns)?,
+                    column_operands: self.column_operands(&columns)?,
+                    columns,
+                })
+            })
+            .collect()
+    }
+}
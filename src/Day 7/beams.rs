@@ -0,0 +1,136 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc::grid::{Coord, Grid};
+
+use crate::{
+    manifold::{Beam, Behavior, Manifold, ManifoldError, Transition},
+    path_count::PathCount,
+};
+
+/// Everything the beams from a set of sources touch.
+pub struct BeamTrace {
+    /// Cells some beam passes through.
+    pub lit: HashSet<Coord>,
+    /// Splitters hit by a vertical beam.
+    pub activated: HashSet<Coord>,
+}
+
+pub(crate) fn trace_beams(manifold: &Manifold, starts: &[Beam]) -> BeamTrace {
+    let mut stack: Vec<Beam> = starts.to_vec();
+    let mut seen: HashSet<Beam> = starts.iter().copied().collect();
+    let mut trace: BeamTrace = BeamTrace {
+        lit: HashSet::new(),
+        activated: HashSet::new(),
+    };
+
+    while let Some(beam) = stack.pop() {
+        trace.lit.insert(beam.coord);
+        if manifold.behavior(beam.coord).is_splitter() && beam.direction.is_vertical() {
+            trace.activated.insert(beam.coord);
+        }
+
+        for transition in manifold.transitions(beam) {
+            if let Transition::Enter(next) = transition
+                && seen.insert(next)
+            {
+                stack.push(next);
+            }
+        }
+    }
+
+    trace
+}
+
+pub struct Timelines<C> {
+    /// Number of timelines passing through each cell.
+    pub per_cell: Grid<C>,
+    pub total: C,
+}
+
+/// Counts timelines by pushing path counts through the beam states reachable
+/// from `starts` in topological order, with one timeline starting at each.
+/// A reachable cycle means some beam never leaves the manifold, so there is
+/// no finite number of timelines.
+pub(crate) fn count_timelines<C: PathCount>(
+    manifold: &Manifold,
+    starts: &[Beam],
+    zero: C,
+    one: C,
+) -> Result<Timelines<C>, ManifoldError> {
+    let mut index: HashMap<Beam, usize> = HashMap::new();
+    let mut beams: Vec<Beam> = Vec::new();
+    let mut incoming: Vec<usize> = Vec::new();
+    let mut counts: Vec<C> = Vec::new();
+
+    for &start in starts {
+        let id: usize = *index.entry(start).or_insert_with(|| {
+            beams.push(start);
+            incoming.push(0);
+            counts.push(zero.clone());
+            beams.len() - 1
+        });
+        counts[id].add_assign(&one);
+    }
+
+    let mut next_index: usize = 0;
+    while next_index < beams.len() {
+        let beam: Beam = beams[next_index];
+        next_index += 1;
+
+        for transition in manifold.transitions(beam) {
+            if let Transition::Enter(next) = transition {
+                let id: usize = *index.entry(next).or_insert_with(|| {
+                    beams.push(next);
+                    incoming.push(0);
+                    counts.push(zero.clone());
+                    beams.len() - 1
+                });
+                incoming[id] += 1;
+            }
+        }
+    }
+
+    let mut ready: Vec<usize> = (0..beams.len())
+        .filter(|&id: &usize| incoming[id] == 0)
+        .collect();
+    let mut processed: usize = 0;
+    let mut per_cell: Grid<C> = manifold.blank(zero.clone());
+    let mut total: C = zero;
+
+    while let Some(id) = ready.pop() {
+        processed += 1;
+        let beam: Beam = beams[id];
+        per_cell[beam.coord].add_assign(&counts[id]);
+        let count: C = if manifold.acts_on(beam) == Behavior::Merge {
+            one.clone()
+        } else {
+            counts[id].clone()
+        };
+
+        for transition in manifold.transitions(beam) {
+            match transition {
+                Transition::Enter(next) => {
+                    let next_id: usize = index[&next];
+                    counts[next_id].add_assign(&count);
+                    incoming[next_id] -= 1;
+                    if incoming[next_id] == 0 {
+                        ready.push(next_id);
+                    }
+                }
+                Transition::Exit => total.add_assign(&count),
+                Transition::Dropped => {}
+            }
+        }
+    }
+
+    if processed < beams.len() {
+        let stuck: usize = (0..beams.len())
+            .find(|&id: &usize| incoming[id] > 0)
+            .expect("Some beam state was never processed");
+        return Err(ManifoldError::Loop {
+            coord: beams[stuck].coord,
+        });
+    }
+
+    Ok(Timelines { per_cell, total })
+}
@@ -1,96 +1,172 @@
-use std::{
-    collections::HashSet,
-    fs::File,
-    io::{BufRead, BufReader},
-    vec,
+mod beams;
+mod manifold;
+mod path_count;
+mod visualize;
+
+use std::{collections::HashMap, fs::read_to_string, num::NonZeroU64, path::PathBuf};
+
+use aoc::{
+    bigint::BigUint,
+    grid::{Coord, Grid},
 };
+use beams::{BeamTrace, Timelines, count_timelines, trace_beams};
+use manifold::{Beam, Boundary, CellTable, Direction, Manifold, ManifoldError};
+use path_count::Modular;
+
+struct Options {
+    modulus: Option<NonZeroU64>,
+    boundary: Boundary,
+    show_splitters: bool,
+    render_dir: Option<PathBuf>,
+    scale: usize,
+    ansi: bool,
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Options {
+    let mut modulus: Option<NonZeroU64> = None;
+    let mut boundary: Boundary = Boundary::Drop;
+    let mut show_splitters: bool = false;
+    let mut render_dir: Option<PathBuf> = None;
+    let mut scale: usize = 4;
+    let mut ansi: bool = false;
 
-fn count_splitted(grid: &[Vec<u8>], starting_point: usize) -> usize {
-    let mut count: usize = 0;
-    let mut stack: Vec<(usize, usize)> = vec![(0, starting_point)];
-    let mut seen: HashSet<(usize, usize)> = HashSet::new();
-    let height: usize = grid.len();
-
-    while let Some((mut current_depth, current_index)) = stack.pop() {
-        while let Some(&character) = grid[current_depth].get(current_index) {
-            if character == b'^' {
-                if seen.contains(&(current_depth, current_index)) {
-                    break;
-                }
-                stack.push((current_depth, current_index + 1));
-                stack.push((current_depth, current_index - 1));
-                seen.insert((current_depth, current_index));
-                count += 1;
-                break;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--splitters" => {
+                show_splitters = true;
+                continue;
+            }
+            "--ansi" => {
+                ansi = true;
+                continue;
             }
+            _ => {}
+        }
 
-            if current_depth + 1 < height {
-                current_depth += 1;
-            } else {
-                break;
+        let value: String = args
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {flag}"));
+
+        match flag.as_str() {
+            "--modulo" => {
+                modulus = Some(value.parse().expect("--modulo needs a positive integer"));
             }
+            "--boundary" => boundary = value.parse().unwrap_or_else(|e| panic!("{e}")),
+            "--render" => render_dir = Some(PathBuf::from(value)),
+            "--scale" => scale = value.parse().expect("Invalid scale"),
+            other => panic!("Unknown option {other}"),
         }
     }
 
-    count
+    Options {
+        modulus,
+        boundary,
+        show_splitters,
+        render_dir,
+        scale,
+        ansi,
+    }
 }
 
-fn count_timelines(grid: &[Vec<u8>], starting_point: usize) -> usize {
-    let height = grid.len();
-    let width = grid[0].len();
+fn main() {
+    let options: Options = parse_options(std::env::args().skip(1));
 
-    let mut curr: Vec<usize> = vec![0usize; width];
-    curr[starting_point] = 1;
+    let grid: Grid<u8> = read_to_string("src/Day 7/input.txt")
+        .expect("Can't open file")
+        .parse()
+        .expect("Manifold rows must have equal width");
 
-    for row in 0..height - 1 {
-        let mut next: Vec<usize> = vec![0usize; width];
+    let sources: Vec<Beam> = grid
+        .iter()
+        .filter(|&(_, &character)| character == b'S')
+        .map(|(coord, _)| Beam::new(coord, Direction::Down))
+        .collect();
+    assert!(!sources.is_empty(), "No beam source found");
 
-        for col in 0..width {
-            let count: usize = curr[col];
-            if count == 0 {
-                continue;
-            }
+    let manifold: Manifold = match Manifold::new(&grid, &CellTable::default()) {
+        Ok(manifold) => manifold.boundary(options.boundary),
+        Err(error) => {
+            println!("Invalid manifold: {error}");
+            std::process::exit(1);
+        }
+    };
 
-            let below = grid[row + 1][col];
-            if below == b'^' {
-                if col > 0 {
-                    next[col - 1] += count;
-                }
-                if col + 1 < width {
-                    next[col + 1] += count;
-                }
-            } else {
-                next[col] += count;
+    let mut reached_by: HashMap<Coord, Vec<usize>> = HashMap::new();
+
+    for (index, source) in sources.iter().enumerate() {
+        let trace: BeamTrace = trace_beams(&manifold, &[*source]);
+        let summary: String = format!(
+            "Source {} at {}: {} splitters",
+            index + 1,
+            source.coord,
+            trace.activated.len()
+        );
+        match count_timelines(&manifold, &[*source], BigUint::zero(), BigUint::from(1u64)) {
+            Ok(timelines) => println!("{summary}, {} timelines", timelines.total),
+            Err(error) => {
+                println!("{summary}");
+                println!(
+                    "Counting timelines from source {} failed: {error}",
+                    index + 1
+                );
             }
         }
 
-        curr = next;
+        for splitter in trace.activated {
+            reached_by.entry(splitter).or_default().push(index + 1);
+        }
     }
 
-    curr.into_iter().sum()
-}
+    println!("The number of splitted paths is: {}", reached_by.len());
 
-fn main() {
-    let lines: Vec<Vec<u8>> =
-        BufReader::new(File::open("src/Day 7/input.txt").expect("Can't open file"))
-            .lines()
-            .map(|line: Result<String, std::io::Error>| {
-                line.expect("Error reading a line").as_bytes().to_vec()
-            })
-            .collect::<Vec<Vec<u8>>>();
-
-    let starting_point = lines[0]
-        .iter()
-        .position(|&character| character == b'S')
-        .expect("Starting point not found");
-
-    println!(
-        "The number of splitted paths is: {}",
-        count_splitted(&lines, starting_point)
-    );
-
-    println!(
-        "The number of timelines is: {}",
-        count_timelines(&lines, starting_point)
-    );
+    let timelines: Result<Timelines<BigUint>, ManifoldError> =
+        count_timelines(&manifold, &sources, BigUint::zero(), BigUint::from(1u64));
+    match &timelines {
+        Ok(timelines) => println!("The number of timelines is: {}", timelines.total),
+        Err(error) => println!("Counting timelines failed: {error}"),
+    }
+
+    if let Some(modulus) = options.modulus {
+        match count_timelines(
+            &manifold,
+            &sources,
+            Modular::new(0, modulus),
+            Modular::new(1, modulus),
+        ) {
+            Ok(timelines) => println!(
+                "The number of timelines modulo {modulus} is: {}",
+                timelines.total
+            ),
+            Err(error) => println!("Counting timelines modulo {modulus} failed: {error}"),
+        }
+    }
+
+    if options.show_splitters {
+        let mut splitters: Vec<(Coord, Vec<usize>)> = reached_by.into_iter().collect();
+        splitters.sort_unstable_by_key(|(coord, _)| (coord.y, coord.x));
+
+        for (coord, sources) in splitters {
+            let names: Vec<String> = sources.iter().map(usize::to_string).collect();
+            println!("Splitter {coord} reached by sources {}", names.join(", "));
+        }
+    }
+
+    if options.ansi || options.render_dir.is_some() {
+        let trace: BeamTrace = trace_beams(&manifold, &sources);
+        let per_cell: Grid<BigUint> = match timelines {
+            Ok(timelines) => timelines.per_cell,
+            Err(_) => manifold.blank(BigUint::zero()),
+        };
+
+        if options.ansi {
+            visualize::print_ansi(&grid, &manifold, &trace, &per_cell)
+                .expect("Failed to print grid");
+        }
+
+        if let Some(dir) = &options.render_dir {
+            visualize::write_images(dir, &grid, &manifold, &trace, &per_cell, options.scale)
+                .expect("Failed to write images");
+            println!("Wrote beams.ppm and heat.ppm to {}", dir.display());
+        }
+    }
 }
@@ -0,0 +1,375 @@
+use std::{fmt, str::FromStr};
+
+use aoc::grid::{Coord, Grid};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    const fn offset(self) -> (isize, isize) {
+        match self {
+            Self::Up => (0, -1),
+            Self::Down => (0, 1),
+            Self::Left => (-1, 0),
+            Self::Right => (1, 0),
+        }
+    }
+
+    pub(crate) const fn is_vertical(self) -> bool {
+        matches!(self, Self::Up | Self::Down)
+    }
+
+    const fn reversed(self) -> Self {
+        match self {
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+            Self::Left => Self::Right,
+            Self::Right => Self::Left,
+        }
+    }
+}
+
+/// What happens to a beam stepping off the left or right edge. Beams leaving
+/// through the top or bottom always end their timeline.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    #[default]
+    Drop,
+    /// Re-enter from the opposite side.
+    Wrap,
+    /// Bounce back: the horizontal component of the step is flipped.
+    Reflect,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBoundaryError(String);
+
+impl fmt::Display for ParseBoundaryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseBoundaryError {}
+
+impl FromStr for Boundary {
+    type Err = ParseBoundaryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "drop" => Ok(Self::Drop),
+            "wrap" => Ok(Self::Wrap),
+            "reflect" => Ok(Self::Reflect),
+            other => Err(ParseBoundaryError(format!("Unknown boundary '{other}'"))),
+        }
+    }
+}
+
+/// What a cell does to a beam entering it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Behavior {
+    Empty,
+    /// Splits a vertical beam into two beams in the neighboring columns.
+    Split,
+    /// Deflects a vertical beam into the column on its left only.
+    SplitLeft,
+    /// Deflects a vertical beam into the column on its right only.
+    SplitRight,
+    /// `/`: turns downward beams left, rightward beams up, and so on.
+    MirrorSlash,
+    /// `\`: turns downward beams right, rightward beams down, and so on.
+    MirrorBackslash,
+    Absorb,
+    /// All timelines passing through collapse into a single one.
+    Merge,
+}
+
+impl Behavior {
+    pub(crate) const fn is_splitter(self) -> bool {
+        matches!(self, Self::Split | Self::SplitLeft | Self::SplitRight)
+    }
+}
+
+/// Maps manifold characters to cell behaviors.
+#[derive(Clone)]
+pub struct CellTable([Option<Behavior>; 256]);
+
+impl CellTable {
+    pub(crate) const fn with(mut self, cell: u8, behavior: Behavior) -> Self {
+        self.0[cell as usize] = Some(behavior);
+        self
+    }
+
+    pub(crate) const fn get(&self, cell: u8) -> Option<Behavior> {
+        self.0[cell as usize]
+    }
+}
+
+impl Default for CellTable {
+    fn default() -> Self {
+        Self([None; 256])
+            .with(b'.', Behavior::Empty)
+            .with(b'S', Behavior::Empty)
+            .with(b'^', Behavior::Split)
+            .with(b'<', Behavior::SplitLeft)
+            .with(b'>', Behavior::SplitRight)
+            .with(b'/', Behavior::MirrorSlash)
+            .with(b'\\', Behavior::MirrorBackslash)
+            .with(b'#', Behavior::Absorb)
+            .with(b'+', Behavior::Merge)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManifoldError {
+    UnknownCell { coord: Coord, cell: u8 },
+    Loop { coord: Coord },
+}
+
+impl fmt::Display for ManifoldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownCell { coord, cell } => {
+                write!(f, "unknown cell '{}' at {coord}", *cell as char)
+            }
+            Self::Loop { coord } => write!(f, "beam loops forever through {coord}"),
+        }
+    }
+}
+
+impl std::error::Error for ManifoldError {}
+
+/// A beam entering `coord` while travelling in `direction`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Beam {
+    pub coord: Coord,
+    pub direction: Direction,
+    /// Split off a splitter next to `coord`; such a beam passes the cell it
+    /// lands on without interacting with it.
+    pub split_off: bool,
+}
+
+impl Beam {
+    pub(crate) const fn new(coord: Coord, direction: Direction) -> Self {
+        Self {
+            coord,
+            direction,
+            split_off: false,
+        }
+    }
+}
+
+/// Where a beam goes after passing through a cell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transition {
+    Enter(Beam),
+    /// The beam left through the top or bottom edge, ending its timeline.
+    Exit,
+    /// The beam left through a side edge under [`Boundary::Drop`] and is lost.
+    Dropped,
+}
+
+pub struct Manifold {
+    cells: Grid<Behavior>,
+    boundary: Boundary,
+}
+
+impl Manifold {
+    pub(crate) fn new(grid: &Grid<u8>, table: &CellTable) -> Result<Self, ManifoldError> {
+        if let Some((coord, &cell)) = grid.iter().find(|&(_, &cell)| table.get(cell).is_none()) {
+            return Err(ManifoldError::UnknownCell { coord, cell });
+        }
+
+        Ok(Self {
+            cells: grid.map(|&cell: &u8| table.get(cell).expect("Checked above")),
+            boundary: Boundary::Drop,
+        })
+    }
+
+    pub(crate) const fn boundary(mut self, boundary: Boundary) -> Self {
+        self.boundary = boundary;
+        self
+    }
+
+    pub(crate) fn behavior(&self, coord: Coord) -> Behavior {
+        self.cells[coord]
+    }
+
+    /// The behavior `beam` meets in the cell it enters.
+    pub(crate) fn acts_on(&self, beam: Beam) -> Behavior {
+        if beam.split_off {
+            Behavior::Empty
+        } else {
+            self.cells[beam.coord]
+        }
+    }
+
+    /// A grid of the manifold's size filled with `fill`.
+    pub(crate) fn blank<T: Clone>(&self, fill: T) -> Grid<T> {
+        Grid::new(self.cells.width(), self.cells.height(), fill)
+    }
+
+    fn move_from(&self, coord: Coord, direction: Direction) -> Transition {
+        let (dx, dy) = direction.offset();
+        if let Some(next) = self.cells.step(coord, dx, dy) {
+            return Transition::Enter(Beam::new(next, direction));
+        }
+        if direction.is_vertical() {
+            return Transition::Exit;
+        }
+
+        let next: Option<Beam> = match self.boundary {
+            Boundary::Drop => None,
+            Boundary::Wrap => self
+                .cells
+                .step_wrapping(coord, dx, dy)
+                .map(|next: Coord| Beam::new(next, direction)),
+            Boundary::Reflect => self
+                .cells
+                .step(coord, -dx, dy)
+                .map(|next: Coord| Beam::new(next, direction.reversed())),
+        };
+        next.map_or(Transition::Dropped, Transition::Enter)
+    }
+
+    /// A beam continuing in `direction` from the column `side` of `coord`.
+    fn side_beam(&self, coord: Coord, side: Direction, direction: Direction) -> Transition {
+        match self.move_from(coord, side) {
+            Transition::Enter(beam) => Transition::Enter(Beam {
+                coord: beam.coord,
+                direction,
+                split_off: true,
+            }),
+            _ => Transition::Dropped,
+        }
+    }
+
+    /// The (at most two) transitions of `beam` through the cell it enters.
+    pub(crate) fn transitions(&self, beam: Beam) -> impl Iterator<Item = Transition> {
+        let Beam {
+            coord, direction, ..
+        } = beam;
+        let vertical: bool = direction.is_vertical();
+
+        let (first, second): (Option<Transition>, Option<Transition>) = match self.acts_on(beam) {
+            Behavior::Absorb => (None, None),
+            Behavior::Split if vertical => (
+                Some(self.side_beam(coord, Direction::Left, direction)),
+                Some(self.side_beam(coord, Direction::Right, direction)),
+            ),
+            Behavior::SplitLeft if vertical => (
+                Some(self.side_beam(coord, Direction::Left, direction)),
+                None,
+            ),
+            Behavior::SplitRight if vertical => (
+                Some(self.side_beam(coord, Direction::Right, direction)),
+                None,
+            ),
+            Behavior::MirrorSlash => {
+                let turned: Direction = match direction {
+                    Direction::Down => Direction::Left,
+                    Direction::Up => Direction::Right,
+                    Direction::Right => Direction::Up,
+                    Direction::Left => Direction::Down,
+                };
+                (Some(self.move_from(coord, turned)), None)
+            }
+            Behavior::MirrorBackslash => {
+                let turned: Direction = match direction {
+                    Direction::Down => Direction::Right,
+                    Direction::Up => Direction::Left,
+                    Direction::Right => Direction::Down,
+                    Direction::Left => Direction::Up,
+                };
+                (Some(self.move_from(coord, turned)), None)
+            }
+            _ => (Some(self.move_from(coord, direction)), None),
+        };
+
+        first.into_iter().chain(second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc::bigint::BigUint;
+
+    use super::*;
+    use crate::beams::{count_timelines, trace_beams};
+
+    /// Activated splitters and total timelines for a beam dropped from the `S`.
+    fn run(rows: &str, boundary: Boundary) -> (usize, Result<BigUint, ManifoldError>) {
+        let grid: Grid<u8> = rows.parse().expect("Test grid is rectangular");
+        let manifold: Manifold = Manifold::new(&grid, &CellTable::default())
+            .expect("Test grid has known cells")
+            .boundary(boundary);
+        let start: Beam = grid
+            .iter()
+            .find(|&(_, &cell)| cell == b'S')
+            .map(|(coord, _)| Beam::new(coord, Direction::Down))
+            .expect("Test grid has a source");
+
+        let activated: usize = trace_beams(&manifold, &[start]).activated.len();
+        let total: Result<BigUint, ManifoldError> =
+            count_timelines(&manifold, &[start], BigUint::zero(), BigUint::from(1u64))
+                .map(|timelines| timelines.total);
+        (activated, total)
+    }
+
+    fn timelines(count: u64) -> Result<BigUint, ManifoldError> {
+        Ok(BigUint::from(count))
+    }
+
+    const LEFT_EDGE: &str = "S...\n^...\n....\n";
+    const RIGHT_EDGE: &str = "...S\n...^\n....\n";
+
+    #[test]
+    fn drop_loses_the_beam_leaving_the_grid() {
+        assert_eq!(run(LEFT_EDGE, Boundary::Drop), (1, timelines(1)));
+        assert_eq!(run(RIGHT_EDGE, Boundary::Drop), (1, timelines(1)));
+    }
+
+    #[test]
+    fn wrap_reenters_on_the_opposite_side() {
+        assert_eq!(run(LEFT_EDGE, Boundary::Wrap), (1, timelines(2)));
+        assert_eq!(run(RIGHT_EDGE, Boundary::Wrap), (1, timelines(2)));
+    }
+
+    #[test]
+    fn wrap_reaches_splitters_across_the_edge() {
+        // The left beam of the first splitter wraps into the last column.
+        assert_eq!(
+            run("S...\n^...\n...^\n....\n", Boundary::Wrap),
+            (2, timelines(3))
+        );
+    }
+
+    #[test]
+    fn reflect_bounces_back_into_the_grid() {
+        assert_eq!(run(LEFT_EDGE, Boundary::Reflect), (1, timelines(2)));
+        assert_eq!(run(RIGHT_EDGE, Boundary::Reflect), (1, timelines(2)));
+    }
+
+    #[test]
+    fn single_column_grid() {
+        let column: &str = "S\n^\n.\n";
+        assert_eq!(run(column, Boundary::Drop), (1, timelines(0)));
+        assert_eq!(run(column, Boundary::Reflect), (1, timelines(0)));
+        // Wrapping puts both beams back onto the splitter, which they pass.
+        assert_eq!(run(column, Boundary::Wrap), (1, timelines(2)));
+    }
+
+    #[test]
+    fn split_beams_pass_adjacent_splitters() {
+        // Each beam lands on the other splitter and keeps moving down.
+        assert_eq!(
+            run(".S..\n....\n.^^.\n....\n", Boundary::Drop),
+            (2, timelines(2))
+        );
+    }
+}
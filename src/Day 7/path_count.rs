@@ -0,0 +1,44 @@
+use std::{fmt, num::NonZeroU64};
+
+use aoc::bigint::BigUint;
+
+/// A number of timelines. Counts double at every split, so they need either
+/// arbitrary precision or modular arithmetic.
+pub trait PathCount: Clone + fmt::Display {
+    fn add_assign(&mut self, other: &Self);
+}
+
+impl PathCount for BigUint {
+    fn add_assign(&mut self, other: &Self) {
+        *self += other;
+    }
+}
+
+/// A count reduced modulo `modulus`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Modular {
+    value: u64,
+    modulus: NonZeroU64,
+}
+
+impl Modular {
+    pub(crate) fn new(value: u64, modulus: NonZeroU64) -> Self {
+        Self {
+            value: value % modulus.get(),
+            modulus,
+        }
+    }
+}
+
+impl PathCount for Modular {
+    fn add_assign(&mut self, other: &Self) {
+        self.value = ((u128::from(self.value) + u128::from(other.value))
+            % u128::from(self.modulus.get())) as u64;
+    }
+}
+
+impl fmt::Display for Modular {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
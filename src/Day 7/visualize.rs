@@ -0,0 +1,130 @@
+use std::{
+    fs,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use aoc::{
+    bigint::BigUint,
+    grid::{Coord, Grid},
+    render::{Rgb, heat, save_ppm, write_ansi},
+};
+
+use crate::{
+    beams::BeamTrace,
+    manifold::{Behavior, Manifold},
+};
+
+const EMPTY: Rgb = [25, 25, 30];
+const DEVICE: Rgb = [110, 110, 120];
+const SOURCE: Rgb = [250, 250, 250];
+const BEAM: Rgb = [90, 200, 240];
+const ACTIVATED: Rgb = [230, 60, 40];
+
+/// The grid with `|` drawn over every empty cell a beam passes through.
+fn overlay(grid: &Grid<u8>, manifold: &Manifold, trace: &BeamTrace) -> Grid<u8> {
+    let mut glyphs: Grid<u8> = grid.clone();
+    for &coord in &trace.lit {
+        if grid[coord] != b'S' && manifold.behavior(coord) == Behavior::Empty {
+            glyphs[coord] = b'|';
+        }
+    }
+    glyphs
+}
+
+fn cell_color(grid: &Grid<u8>, manifold: &Manifold, trace: &BeamTrace, coord: Coord) -> Rgb {
+    if grid[coord] == b'S' {
+        SOURCE
+    } else if trace.activated.contains(&coord) {
+        ACTIVATED
+    } else if manifold.behavior(coord) != Behavior::Empty {
+        DEVICE
+    } else if trace.lit.contains(&coord) {
+        BEAM
+    } else {
+        EMPTY
+    }
+}
+
+/// Position of `count` on a log scale up to `max_bits`, or `None` if no
+/// timeline passes through.
+fn heat_level(count: &BigUint, max_bits: usize) -> Option<f64> {
+    if count.is_zero() {
+        return None;
+    }
+    Some((count.bit_len() - 1) as f64 / max_bits.saturating_sub(1).max(1) as f64)
+}
+
+fn max_bits(timelines: &Grid<BigUint>) -> usize {
+    timelines
+        .iter()
+        .map(|(_, count): (Coord, &BigUint)| count.bit_len())
+        .max()
+        .unwrap_or(0)
+}
+
+/// Every cell a timeline passes through colored by how many do, few in red
+/// and many in blue.
+fn heat_frame(grid: &Grid<u8>, manifold: &Manifold, timelines: &Grid<BigUint>) -> Grid<Rgb> {
+    let max_bits: usize = max_bits(timelines);
+    let mut pixels: Grid<Rgb> = grid.map(|_| EMPTY);
+    for (coord, count) in timelines.iter() {
+        pixels[coord] = match heat_level(count, max_bits) {
+            Some(level) => heat(level),
+            None if manifold.behavior(coord) != Behavior::Empty => DEVICE,
+            None => EMPTY,
+        };
+    }
+    pixels
+}
+
+/// Writes the beam paths to `beams.ppm` and the timeline heat map to
+/// `heat.ppm` in `dir`.
+pub(crate) fn write_images(
+    dir: &Path,
+    grid: &Grid<u8>,
+    manifold: &Manifold,
+    trace: &BeamTrace,
+    timelines: &Grid<BigUint>,
+    scale: usize,
+) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+
+    let mut beams: Grid<Rgb> = grid.map(|_| EMPTY);
+    for coord in grid.coords() {
+        beams[coord] = cell_color(grid, manifold, trace, coord);
+    }
+    save_ppm(&dir.join("beams.ppm"), &beams, scale)?;
+
+    save_ppm(
+        &dir.join("heat.ppm"),
+        &heat_frame(grid, manifold, timelines),
+        scale,
+    )
+}
+
+/// Prints the grid with beam paths drawn in, beams colored by their timeline
+/// count and activated splitters highlighted.
+pub(crate) fn print_ansi(
+    grid: &Grid<u8>,
+    manifold: &Manifold,
+    trace: &BeamTrace,
+    timelines: &Grid<BigUint>,
+) -> io::Result<()> {
+    let max_bits: usize = max_bits(timelines);
+    let mut colors: Grid<Option<Rgb>> = grid.map(|_| None);
+
+    for coord in grid.coords() {
+        colors[coord] = match cell_color(grid, manifold, trace, coord) {
+            EMPTY | DEVICE => None,
+            BEAM => heat_level(&timelines[coord], max_bits)
+                .map(heat)
+                .or(Some(BEAM)),
+            color => Some(color),
+        };
+    }
+
+    let mut out: BufWriter<io::StdoutLock<'_>> = BufWriter::new(io::stdout().lock());
+    write_ansi(&mut out, &overlay(grid, manifold, trace), &colors)?;
+    out.flush()
+}
@@ -0,0 +1,98 @@
+use std::{fmt, str::FromStr};
+
+use aoc::bigint::BigUint;
+
+use crate::{edge::Edge, options::ParseOptionError, union_find::UnionFind};
+
+/// How the sizes of the largest circuits are combined into one answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Combine {
+    Product,
+    Sum,
+    Min,
+    Max,
+}
+
+impl FromStr for Combine {
+    type Err = ParseOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Self::Product),
+            "sum" => Ok(Self::Sum),
+            "min" => Ok(Self::Min),
+            "max" => Ok(Self::Max),
+            other => Err(ParseOptionError(format!("Unknown operation '{other}'"))),
+        }
+    }
+}
+
+impl fmt::Display for Combine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name: &str = match self {
+            Self::Product => "product",
+            Self::Sum => "sum",
+            Self::Min => "minimum",
+            Self::Max => "maximum",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Combine {
+    /// Combines `sizes` exactly; a product of many circuits quickly outgrows
+    /// any fixed-width integer.
+    fn apply(self, sizes: &[usize]) -> BigUint {
+        let sizes = sizes.iter().map(|&size: &usize| BigUint::from(size as u64));
+        match self {
+            Self::Product => sizes.fold(BigUint::from(1u64), |product: BigUint, size: BigUint| {
+                &product * &size
+            }),
+            Self::Sum => sizes.fold(BigUint::zero(), |mut sum: BigUint, size: BigUint| {
+                sum += &size;
+                sum
+            }),
+            Self::Min => sizes.min().unwrap_or_default(),
+            Self::Max => sizes.max().unwrap_or_default(),
+        }
+    }
+}
+
+/// Which circuits to measure after wiring up the shortest connections.
+#[derive(Clone, Copy, Debug)]
+pub struct Clustering {
+    pub connections: usize,
+    pub circuits: usize,
+    pub combine: Combine,
+}
+
+impl Default for Clustering {
+    fn default() -> Self {
+        Self {
+            connections: 1000,
+            circuits: 3,
+            combine: Combine::Product,
+        }
+    }
+}
+
+impl Clustering {
+    /// Applies the `connections` shortest edges, then combines the sizes of the
+    /// `circuits` largest resulting circuits.
+    pub(crate) fn largest_circuits(
+        &self,
+        point_count: usize,
+        edges: impl Iterator<Item = Edge>,
+    ) -> BigUint {
+        let mut union_find: UnionFind = UnionFind::new(point_count);
+
+        for edge in edges.take(self.connections) {
+            union_find.union(edge.a, edge.b);
+        }
+
+        let mut component_sizes: Vec<usize> = union_find.all_sizes();
+        component_sizes.sort_unstable_by(|a: &usize, b: &usize| b.cmp(a));
+        component_sizes.truncate(self.circuits);
+        self.combine.apply(&component_sizes)
+    }
+}
@@ -0,0 +1,13 @@
+/// A connection between the points at indices `a < b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Edge {
+    pub a: usize,
+    pub b: usize,
+    pub weight: u128,
+}
+
+impl Edge {
+    pub(crate) const fn new(a: usize, b: usize, weight: u128) -> Self {
+        Self { a, b, weight }
+    }
+}
@@ -0,0 +1,331 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{edge::Edge, metric::Metric, point_3d::Point3D};
+
+/// A static k-d tree stored implicitly: the node for a slice of `order` is its
+/// middle element, with the slice partitioned around it along axis `depth % 3`.
+pub struct KdTree<'a> {
+    points: &'a [Point3D],
+    metric: &'a dyn Metric,
+    order: Vec<usize>,
+}
+
+impl<'a> KdTree<'a> {
+    pub(crate) fn new(points: &'a [Point3D], metric: &'a dyn Metric) -> Self {
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut order, 0);
+        Self {
+            points,
+            metric,
+            order,
+        }
+    }
+
+    fn build(points: &[Point3D], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+
+        let mid: usize = order.len() / 2;
+        let axis: usize = depth % 3;
+        order.select_nth_unstable_by_key(mid, |&index: &usize| points[index].axis(axis));
+
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    /// The `k` points closest to `points[target]`, itself included, as
+    /// `(distance, index)` pairs ordered by distance and then index.
+    pub(crate) fn nearest(&self, target: usize, k: usize) -> Vec<(u128, usize)> {
+        let mut best: BinaryHeap<(u128, usize)> = BinaryHeap::with_capacity(k + 1);
+        self.search(&self.order, 0, &self.points[target], k, &mut best);
+        best.into_sorted_vec()
+    }
+
+    fn search(
+        &self,
+        order: &[usize],
+        depth: usize,
+        target: &Point3D,
+        k: usize,
+        best: &mut BinaryHeap<(u128, usize)>,
+    ) {
+        if order.is_empty() || k == 0 {
+            return;
+        }
+
+        let mid: usize = order.len() / 2;
+        let index: usize = order[mid];
+        let point: &Point3D = &self.points[index];
+
+        let candidate: (u128, usize) = (self.metric.distance(target.offsets(point)), index);
+        if best.len() < k {
+            best.push(candidate);
+        } else if best
+            .peek()
+            .is_some_and(|worst: &(u128, usize)| candidate < *worst)
+        {
+            best.pop();
+            best.push(candidate);
+        }
+
+        let axis: usize = depth % 3;
+        let offset: isize = target.axis(axis) - point.axis(axis);
+        let (near, far): (&[usize], &[usize]) = if offset < 0 {
+            (&order[..mid], &order[mid + 1..])
+        } else {
+            (&order[mid + 1..], &order[..mid])
+        };
+
+        self.search(near, depth + 1, target, k, best);
+
+        let bound: u128 = self.metric.axis_bound(axis, offset.unsigned_abs() as u128);
+        if best.len() < k || best.peek().is_some_and(|&(worst, _)| bound <= worst) {
+            self.search(far, depth + 1, target, k, best);
+        }
+    }
+}
+
+const INITIAL_NEIGHBORS: usize = 8;
+
+/// Neighbors `j > i` of one point `i` in increasing distance, fetched from
+/// the tree in batches that double in size.
+#[derive(Default)]
+struct Neighbors {
+    found: Vec<(u128, usize)>,
+    next: usize,
+    k: usize,
+}
+
+/// Every pair of points as an [`Edge`], ordered by distance under the metric,
+/// then by the smaller point index, then by the larger one. Pairs are
+/// produced lazily, so taking the first few edges only looks at a few
+/// neighbors of each point.
+pub struct ClosestPairs<'a> {
+    tree: KdTree<'a>,
+    neighbors: Vec<Neighbors>,
+    /// The next unreturned edge of every point that still has one.
+    heap: BinaryHeap<Reverse<(u128, usize, usize)>>,
+}
+
+impl<'a> ClosestPairs<'a> {
+    pub(crate) fn new(points: &'a [Point3D], metric: &'a dyn Metric) -> Self {
+        let mut pairs: Self = Self {
+            tree: KdTree::new(points, metric),
+            neighbors: (0..points.len()).map(|_| Neighbors::default()).collect(),
+            heap: BinaryHeap::new(),
+        };
+
+        for index in 0..points.len() {
+            pairs.queue_next(index);
+        }
+
+        pairs
+    }
+
+    fn queue_next(&mut self, index: usize) {
+        let total: usize = self.neighbors.len();
+        let neighbors: &mut Neighbors = &mut self.neighbors[index];
+
+        while neighbors.next == neighbors.found.len() {
+            if neighbors.k == total {
+                return;
+            }
+
+            // The (distance, index) order is total, so a larger batch starts
+            // with the neighbors already returned.
+            neighbors.k = (neighbors.k * 2).clamp(INITIAL_NEIGHBORS.min(total), total);
+            neighbors.found = self
+                .tree
+                .nearest(index, neighbors.k)
+                .into_iter()
+                .filter(|&(_, other): &(u128, usize)| other > index)
+                .collect();
+        }
+
+        let (weight, other) = neighbors.found[neighbors.next];
+        neighbors.next += 1;
+        self.heap.push(Reverse((weight, index, other)));
+    }
+}
+
+impl Iterator for ClosestPairs<'_> {
+    type Item = Edge;
+
+    fn next(&mut self) -> Option<Edge> {
+        let Reverse((weight, a, b)) = self.heap.pop()?;
+        self.queue_next(a);
+        Some(Edge::new(a, b, weight))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metric::{Chebyshev, Euclidean, Manhattan, Weighted};
+
+    /// A small deterministic generator, so failures are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn coordinate(&mut self, bound: u64) -> isize {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((self.0 >> 33) % bound) as isize
+        }
+    }
+
+    /// Every pair as `(weight, a, b)`, sorted the way `ClosestPairs` yields them.
+    fn brute_force(points: &[Point3D], metric: &dyn Metric) -> Vec<(u128, usize, usize)> {
+        let mut pairs: Vec<(u128, usize, usize)> = Vec::new();
+        for a in 0..points.len() {
+            for b in a + 1..points.len() {
+                pairs.push((metric.distance(points[a].offsets(&points[b])), a, b));
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    fn closest_pairs(points: &[Point3D], metric: &dyn Metric) -> Vec<(u128, usize, usize)> {
+        ClosestPairs::new(points, metric)
+            .map(|edge: Edge| (edge.weight, edge.a, edge.b))
+            .collect()
+    }
+
+    fn random_points(rng: &mut Lcg, count: usize, bound: u64) -> Vec<Point3D> {
+        (0..count)
+            .map(|_| {
+                Point3D::new(
+                    rng.coordinate(bound),
+                    rng.coordinate(bound),
+                    rng.coordinate(bound),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_sorted_pairs() {
+        let mut rng: Lcg = Lcg(46);
+        for count in [0, 1, 2, 3, 9, 17, 40, 90] {
+            let points: Vec<Point3D> = random_points(&mut rng, count, 1000);
+            assert_eq!(
+                closest_pairs(&points, &Euclidean),
+                brute_force(&points, &Euclidean),
+                "{count} points"
+            );
+        }
+    }
+
+    #[test]
+    fn matches_sorted_pairs_with_duplicates() {
+        let mut rng: Lcg = Lcg(8);
+        // A tiny coordinate range makes repeated points and equal distances common.
+        for count in [2, 5, 30, 80] {
+            let points: Vec<Point3D> = random_points(&mut rng, count, 3);
+            assert_eq!(
+                closest_pairs(&points, &Euclidean),
+                brute_force(&points, &Euclidean),
+                "{count} points"
+            );
+        }
+
+        let same: Vec<Point3D> = (0..12).map(|_| Point3D::new(5, -5, 5)).collect();
+        assert_eq!(
+            closest_pairs(&same, &Euclidean),
+            brute_force(&same, &Euclidean)
+        );
+    }
+
+    fn weighted(weights: [u64; 3]) -> Weighted {
+        Weighted {
+            metric: Box::new(Manhattan),
+            weights,
+        }
+    }
+
+    #[test]
+    fn ties_break_by_smaller_then_larger_index() {
+        let corners: Vec<Point3D> = vec![
+            Point3D::new(1, 1, 1),
+            Point3D::new(0, 0, 1),
+            Point3D::new(0, 1, 0),
+            Point3D::new(1, 0, 0),
+            Point3D::new(0, 0, 0),
+        ];
+        assert_eq!(
+            closest_pairs(&corners, &Manhattan),
+            [
+                (1, 1, 4),
+                (1, 2, 4),
+                (1, 3, 4),
+                (2, 0, 1),
+                (2, 0, 2),
+                (2, 0, 3),
+                (2, 1, 2),
+                (2, 1, 3),
+                (2, 2, 3),
+                (3, 0, 4),
+            ]
+        );
+        assert_eq!(
+            closest_pairs(&corners, &Chebyshev),
+            [
+                (1, 0, 1),
+                (1, 0, 2),
+                (1, 0, 3),
+                (1, 0, 4),
+                (1, 1, 2),
+                (1, 1, 3),
+                (1, 1, 4),
+                (1, 2, 3),
+                (1, 2, 4),
+                (1, 3, 4),
+            ]
+        );
+
+        // Doubling x makes the unit step along it tie with two steps along y or z.
+        let stretched: Vec<Point3D> = vec![
+            Point3D::new(1, 2, 2),
+            Point3D::new(0, 0, 2),
+            Point3D::new(0, 2, 0),
+            Point3D::new(1, 0, 0),
+            Point3D::new(0, 0, 0),
+        ];
+        assert_eq!(
+            closest_pairs(&stretched, &weighted([2, 1, 1])),
+            [
+                (2, 1, 4),
+                (2, 2, 4),
+                (2, 3, 4),
+                (4, 0, 1),
+                (4, 0, 2),
+                (4, 0, 3),
+                (4, 1, 2),
+                (4, 1, 3),
+                (4, 2, 3),
+                (6, 0, 4),
+            ]
+        );
+    }
+
+    #[test]
+    fn ties_match_sorted_pairs_under_every_metric() {
+        let mut rng: Lcg = Lcg(49);
+        let metrics: [&dyn Metric; 4] = [&Manhattan, &Chebyshev, &weighted([2, 1, 3]), &Euclidean];
+        for metric in metrics {
+            for count in [5, 30, 80] {
+                let points: Vec<Point3D> = random_points(&mut rng, count, 4);
+                assert_eq!(
+                    closest_pairs(&points, metric),
+                    brute_force(&points, metric),
+                    "{count} points"
+                );
+            }
+        }
+    }
+}
@@ -1,102 +1,155 @@
+mod clustering;
+mod edge;
+mod kd_tree;
+mod metric;
+mod mst;
+mod options;
 mod point_3d;
 mod union_find;
 
 use std::{
     fs::File,
-    io::{BufRead, BufReader},
+    io::{self, BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
 };
 
+use clustering::Clustering;
+use edge::Edge;
+use kd_tree::ClosestPairs;
+use metric::{Metric, MetricKind, Weighted, parse_weights};
+use mst::SpanningTree;
 use point_3d::Point3D;
-use union_find::UnionFind;
 
-struct Edge {
-    a: usize,
-    b: usize,
-    weight: u64,
+fn export(
+    path: &Path,
+    write: impl FnOnce(&mut BufWriter<File>) -> io::Result<()>,
+) -> io::Result<()> {
+    let mut out: BufWriter<File> = BufWriter::new(File::create(path)?);
+    write(&mut out)?;
+    out.flush()
 }
 
-impl Edge {
-    const fn new(a: usize, b: usize, weight: u64) -> Self {
-        Self { a, b, weight }
-    }
+struct Options {
+    input: PathBuf,
+    clustering: Clustering,
+    metric: MetricKind,
+    weights: Option<[u64; 3]>,
+    show_tree: bool,
+    csv: Option<PathBuf>,
+    dot: Option<PathBuf>,
 }
 
-fn build_distance_matrix(points: &[Point3D]) -> Vec<Edge> {
-    let mut edges: Vec<Edge> = Vec::new();
-    for i in 0..points.len() {
-        for j in (i + 1)..points.len() {
-            let dx: u64 = (points[i].x - points[j].x).unsigned_abs() as u64;
-            let dy: u64 = (points[i].y - points[j].y).unsigned_abs() as u64;
-            let dz: u64 = (points[i].z - points[j].z).unsigned_abs() as u64;
-
-            edges.push(Edge::new(i, j, dx * dx + dy * dy + dz * dz));
+fn parse_options(mut args: impl Iterator<Item = String>) -> Options {
+    let mut input: PathBuf = PathBuf::from("src/Day 8/input.txt");
+    let mut clustering: Clustering = Clustering::default();
+    let mut metric: MetricKind = MetricKind::default();
+    let mut weights: Option<[u64; 3]> = None;
+    let mut show_tree: bool = false;
+    let mut csv: Option<PathBuf> = None;
+    let mut dot: Option<PathBuf> = None;
+
+    while let Some(flag) = args.next() {
+        if flag == "--mst" {
+            show_tree = true;
+            continue;
         }
-    }
-    edges
-}
-
-fn largest_3_components_multiplied(points: &[Point3D], edges: &[Edge]) -> usize {
-    let size: usize = points.len();
-    let mut union_find: UnionFind = UnionFind::new(size);
-
-    for edge in edges.iter().take(1000) {
-        union_find.union(edge.a, edge.b);
-    }
-
-    let mut component_sizes: Vec<usize> = union_find.all_sizes();
-    component_sizes.sort_unstable_by(|a: &usize, b: &usize| b.cmp(a));
-    component_sizes.iter().take(3).product()
-}
-
-fn multiply_last_x_elements(points: &[Point3D], edges: &[Edge]) -> isize {
-    let size: usize = points.len();
-    let mut union_find: UnionFind = UnionFind::new(points.len());
-    let mut remaining_components: usize = size;
 
-    for edge in edges {
-        if union_find.union(edge.a, edge.b) {
-            remaining_components -= 1;
+        let value: String = args
+            .next()
+            .unwrap_or_else(|| panic!("Missing value for {flag}"));
 
-            if remaining_components == 1 {
-                return points[edge.a].x * points[edge.b].x;
+        match flag.as_str() {
+            "--input" => input = PathBuf::from(value),
+            "--connections" => {
+                clustering.connections = value.parse().expect("Invalid connection count");
             }
+            "--circuits" => clustering.circuits = value.parse().expect("Invalid circuit count"),
+            "--combine" => clustering.combine = value.parse().unwrap_or_else(|e| panic!("{e}")),
+            "--metric" => metric = value.parse().unwrap_or_else(|e| panic!("{e}")),
+            "--weights" => weights = Some(parse_weights(&value).unwrap_or_else(|e| panic!("{e}"))),
+            "--csv" => csv = Some(PathBuf::from(value)),
+            "--dot" => dot = Some(PathBuf::from(value)),
+            other => panic!("Unknown option {other}"),
         }
     }
 
-    0
+    Options {
+        input,
+        clustering,
+        metric,
+        weights,
+        show_tree,
+        csv,
+        dot,
+    }
 }
 
 fn main() {
+    let options: Options = parse_options(std::env::args().skip(1));
     let mut points: Vec<Point3D> = Vec::new();
 
-    for line in BufReader::new(File::open("src/Day 8/input.txt").expect("Can't open file")).lines()
-    {
-        match line {
-            Ok(content) => {
-                let numbers: Vec<isize> = content
-                    .split(',')
-                    .map(|s: &str| s.trim().parse::<isize>().expect("Failure to parse integer"))
-                    .collect();
-                if numbers.len() == 3 {
-                    points.push(Point3D::new(numbers[0], numbers[1], numbers[2]));
-                } else {
-                    eprintln!("Expected 3 numbers per line, got {numbers:?}");
-                }
+    let reader: BufReader<File> =
+        BufReader::new(File::open(&options.input).expect("Can't open file"));
+    for (index, line) in reader.lines().enumerate() {
+        let content: String = line.expect("Failed to read line");
+        if content.trim().is_empty() {
+            continue;
+        }
+
+        match content.parse::<Point3D>() {
+            Ok(point) => points.push(point),
+            Err(error) => {
+                eprintln!("Invalid input: line {}: {error}", index + 1);
+                std::process::exit(1);
             }
-            Err(e) => eprintln!("Error reading line: {e}"),
         }
     }
 
-    let mut edges: Vec<Edge> = build_distance_matrix(&points);
-    edges.sort_unstable_by_key(|edge: &Edge| edge.weight);
+    let metric: Box<dyn Metric> = match options.weights {
+        Some(weights) => Box::new(Weighted {
+            metric: options.metric.metric(),
+            weights,
+        }),
+        None => options.metric.metric(),
+    };
+
+    if metric.checked_distance(point_3d::extent(&points)).is_none() {
+        eprintln!("Invalid input: distances between these points overflow u128");
+        std::process::exit(1);
+    }
 
+    let clustering: Clustering = options.clustering;
     println!(
-        "The product of the sizes of the three largest components is {}",
-        largest_3_components_multiplied(&points, &edges)
+        "The {} of the sizes of the {} largest components is {}",
+        clustering.combine,
+        clustering.circuits,
+        clustering.largest_circuits(points.len(), ClosestPairs::new(&points, metric.as_ref()))
     );
 
+    let tree: SpanningTree =
+        SpanningTree::build(points.len(), ClosestPairs::new(&points, metric.as_ref()));
     println!(
         "The product of the x coordinates of the first two points is {}",
-        multiply_last_x_elements(&points, &edges)
+        tree.last_edge()
+            .map_or(0, |edge: &Edge| points[edge.a].x as i128
+                * points[edge.b].x as i128)
     );
+
+    if options.show_tree {
+        tree.print(&points);
+    }
+
+    if let Some(path) = &options.csv {
+        export(path, |out: &mut BufWriter<File>| {
+            tree.write_csv(out, &points)
+        })
+        .expect("Failed to write CSV");
+    }
+
+    if let Some(path) = &options.dot {
+        export(path, |out: &mut BufWriter<File>| {
+            tree.write_dot(out, &points)
+        })
+        .expect("Failed to write DOT");
+    }
 }
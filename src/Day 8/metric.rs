@@ -0,0 +1,124 @@
+use std::str::FromStr;
+
+use crate::options::ParseOptionError;
+
+/// A distance between two points, computed from the absolute differences of
+/// their coordinates along x, y and z.
+///
+/// The distance must not decrease when any single offset grows; the k-d tree
+/// relies on this to skip subtrees. Any such closure is a metric too.
+///
+/// Weights can push a distance past `u128`, so metrics report overflow from
+/// `checked_distance`. By monotonicity, a metric that measures the bounding
+/// box of the input without overflowing measures every pair of its points.
+pub trait Metric {
+    fn checked_distance(&self, offsets: [u128; 3]) -> Option<u128>;
+
+    fn distance(&self, offsets: [u128; 3]) -> u128 {
+        self.checked_distance(offsets)
+            .expect("Distance overflows u128")
+    }
+
+    /// A lower bound on the distance to any point that is `gap` away along `axis`.
+    fn axis_bound(&self, axis: usize, gap: u128) -> u128 {
+        let mut offsets: [u128; 3] = [0; 3];
+        offsets[axis] = gap;
+        self.distance(offsets)
+    }
+}
+
+impl<F: Fn([u128; 3]) -> u128> Metric for F {
+    fn checked_distance(&self, offsets: [u128; 3]) -> Option<u128> {
+        Some(self(offsets))
+    }
+}
+
+/// Squared Euclidean distance, which orders pairs like the Euclidean one
+/// without taking square roots.
+pub struct Euclidean;
+
+impl Metric for Euclidean {
+    fn checked_distance(&self, offsets: [u128; 3]) -> Option<u128> {
+        offsets
+            .iter()
+            .map(|&offset: &u128| offset.checked_mul(offset))
+            .try_fold(0, |sum: u128, square: Option<u128>| {
+                sum.checked_add(square?)
+            })
+    }
+}
+
+pub struct Manhattan;
+
+impl Metric for Manhattan {
+    fn checked_distance(&self, [dx, dy, dz]: [u128; 3]) -> Option<u128> {
+        dx.checked_add(dy)?.checked_add(dz)
+    }
+}
+
+pub struct Chebyshev;
+
+impl Metric for Chebyshev {
+    fn checked_distance(&self, [dx, dy, dz]: [u128; 3]) -> Option<u128> {
+        Some(dx.max(dy).max(dz))
+    }
+}
+
+/// Scales each axis before measuring with `metric`.
+pub struct Weighted {
+    pub metric: Box<dyn Metric>,
+    pub weights: [u64; 3],
+}
+
+impl Metric for Weighted {
+    fn checked_distance(&self, offsets: [u128; 3]) -> Option<u128> {
+        let scale = |axis: usize| offsets[axis].checked_mul(u128::from(self.weights[axis]));
+        self.metric
+            .checked_distance([scale(0)?, scale(1)?, scale(2)?])
+    }
+}
+
+/// The metrics selectable by name on the command line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MetricKind {
+    #[default]
+    Euclidean,
+    Manhattan,
+    Chebyshev,
+}
+
+impl FromStr for MetricKind {
+    type Err = ParseOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "euclidean" => Ok(Self::Euclidean),
+            "manhattan" => Ok(Self::Manhattan),
+            "chebyshev" => Ok(Self::Chebyshev),
+            other => Err(ParseOptionError(format!("Unknown metric '{other}'"))),
+        }
+    }
+}
+
+impl MetricKind {
+    pub(crate) fn metric(self) -> Box<dyn Metric> {
+        match self {
+            Self::Euclidean => Box::new(Euclidean),
+            Self::Manhattan => Box::new(Manhattan),
+            Self::Chebyshev => Box::new(Chebyshev),
+        }
+    }
+}
+
+/// Parses per-axis weights written as `x,y,z`.
+pub(crate) fn parse_weights(s: &str) -> Result<[u64; 3], ParseOptionError> {
+    let weights: Vec<u64> = s
+        .split(',')
+        .map(|weight: &str| weight.trim().parse())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| ParseOptionError(format!("Invalid weights '{s}'")))?;
+
+    weights
+        .try_into()
+        .map_err(|_| ParseOptionError(format!("Expected three weights, got '{s}'")))
+}
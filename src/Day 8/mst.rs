@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+
+use aoc::bigint::BigUint;
+
+use crate::{edge::Edge, point_3d::Point3D, union_find::UnionFind};
+
+/// One edge of the spanning tree and the size of the circuit it completed.
+pub struct Merge {
+    pub edge: Edge,
+    pub circuit_size: usize,
+}
+
+/// A minimum spanning tree with its edges in the order Kruskal's algorithm
+/// merged them.
+pub struct SpanningTree {
+    pub merges: Vec<Merge>,
+    pub total_weight: BigUint,
+}
+
+impl SpanningTree {
+    /// Kruskal's algorithm over `edges`, which must come in increasing weight.
+    /// Stops reading edges as soon as every point is connected.
+    pub(crate) fn build(point_count: usize, edges: impl Iterator<Item = Edge>) -> Self {
+        let mut union_find: UnionFind = UnionFind::new(point_count);
+        let mut merges: Vec<Merge> = Vec::with_capacity(point_count.saturating_sub(1));
+        let mut total_weight: BigUint = BigUint::zero();
+
+        for edge in edges {
+            if merges.len() + 1 >= point_count {
+                break;
+            }
+
+            if union_find.union(edge.a, edge.b) {
+                total_weight += &BigUint::from(edge.weight);
+                merges.push(Merge {
+                    edge,
+                    circuit_size: union_find.size_of(edge.a),
+                });
+            }
+        }
+
+        Self {
+            merges,
+            total_weight,
+        }
+    }
+
+    /// The edge that joined the last two circuits.
+    pub(crate) fn last_edge(&self) -> Option<&Edge> {
+        self.merges.last().map(|merge: &Merge| &merge.edge)
+    }
+
+    pub(crate) fn print(&self, points: &[Point3D]) {
+        for (step, merge) in self.merges.iter().enumerate() {
+            let Edge { a, b, weight } = merge.edge;
+            println!(
+                "Step {}: {} - {} (weight {weight}), circuit of {}",
+                step + 1,
+                points[a],
+                points[b],
+                merge.circuit_size
+            );
+        }
+        println!(
+            "Minimum spanning tree: {} edges, total weight {}",
+            self.merges.len(),
+            self.total_weight
+        );
+    }
+
+    pub(crate) fn write_csv<W: Write>(&self, out: &mut W, points: &[Point3D]) -> io::Result<()> {
+        writeln!(out, "step,a,b,ax,ay,az,bx,by,bz,weight,circuit_size")?;
+        for (step, merge) in self.merges.iter().enumerate() {
+            let Edge { a, b, weight } = merge.edge;
+            let (pa, pb): (&Point3D, &Point3D) = (&points[a], &points[b]);
+            writeln!(
+                out,
+                "{},{a},{b},{},{},{},{},{},{},{weight},{}",
+                step + 1,
+                pa.x,
+                pa.y,
+                pa.z,
+                pb.x,
+                pb.y,
+                pb.z,
+                merge.circuit_size
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the tree as an undirected Graphviz graph, one node per junction
+    /// box labelled with its coordinates.
+    pub(crate) fn write_dot<W: Write>(&self, out: &mut W, points: &[Point3D]) -> io::Result<()> {
+        writeln!(out, "graph mst {{")?;
+        for (index, point) in points.iter().enumerate() {
+            writeln!(out, "    {index} [label=\"{point}\"];")?;
+        }
+        for merge in &self.merges {
+            let Edge { a, b, weight } = merge.edge;
+            writeln!(out, "    {a} -- {b} [label=\"{weight}\"];")?;
+        }
+        writeln!(out, "}}")
+    }
+}
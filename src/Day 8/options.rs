@@ -0,0 +1,13 @@
+use std::fmt;
+
+/// An invalid value for one of Day 8's command-line options.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseOptionError(pub(crate) String);
+
+impl fmt::Display for ParseOptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseOptionError {}
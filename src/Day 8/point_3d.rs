@@ -1,3 +1,31 @@
+use std::{fmt, str::FromStr};
+
+/// Largest coordinate magnitude accepted, so that differences along an axis
+/// fit in `isize` and squared distances in `u128`.
+pub const MAX_COORDINATE: isize = (1 << 62) - 1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePointError {
+    WrongArity(usize),
+    InvalidNumber(String),
+    OutOfRange(String),
+}
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongArity(count) => write!(f, "expected 3 coordinates, got {count}"),
+            Self::InvalidNumber(text) => write!(f, "invalid coordinate '{text}'"),
+            Self::OutOfRange(text) => write!(
+                f,
+                "coordinate {text} is outside -{MAX_COORDINATE}..={MAX_COORDINATE}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
 pub struct Point3D {
     pub x: isize,
     pub y: isize,
@@ -8,10 +36,67 @@ impl Point3D {
     pub(crate) const fn new(x: isize, y: isize, z: isize) -> Self {
         Self { x, y, z }
     }
+
+    /// Coordinate along axis 0 (x), 1 (y) or 2 (z).
+    pub(crate) const fn axis(&self, axis: usize) -> isize {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    /// Absolute coordinate differences to `other` along x, y and z.
+    pub(crate) const fn offsets(&self, other: &Self) -> [u128; 3] {
+        [
+            (self.x - other.x).unsigned_abs() as u128,
+            (self.y - other.y).unsigned_abs() as u128,
+            (self.z - other.z).unsigned_abs() as u128,
+        ]
+    }
+}
+
+/// Side lengths of the smallest box containing all `points`, which bound the
+/// offsets between any two of them.
+pub(crate) fn extent(points: &[Point3D]) -> [u128; 3] {
+    let side = |axis: usize| {
+        let coords = points.iter().map(|point: &Point3D| point.axis(axis));
+        match (coords.clone().min(), coords.max()) {
+            (Some(min), Some(max)) => (max - min).unsigned_abs() as u128,
+            _ => 0,
+        }
+    };
+    [side(0), side(1), side(2)]
+}
+
+impl FromStr for Point3D {
+    type Err = ParsePointError;
+
+    /// Parses `x,y,z`, rejecting coordinates beyond [`MAX_COORDINATE`].
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers: Vec<isize> = s
+            .split(',')
+            .map(|text: &str| {
+                let text: &str = text.trim();
+                let value: i128 = text
+                    .parse()
+                    .map_err(|_| ParsePointError::InvalidNumber(text.to_string()))?;
+                if value.unsigned_abs() > MAX_COORDINATE as u128 {
+                    return Err(ParsePointError::OutOfRange(text.to_string()));
+                }
+                Ok(value as isize)
+            })
+            .collect::<Result<Vec<isize>, ParsePointError>>()?;
+
+        match numbers[..] {
+            [x, y, z] => Ok(Self::new(x, y, z)),
+            _ => Err(ParsePointError::WrongArity(numbers.len())),
+        }
+    }
 }
 
-impl std::fmt::Display for Point3D {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl fmt::Display for Point3D {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "({}, {}, {})", self.x, self.y, self.z)
     }
 }
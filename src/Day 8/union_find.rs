@@ -41,6 +41,12 @@ impl UnionFind {
         true
     }
 
+    /// Size of the set containing `x`.
+    pub(crate) fn size_of(&mut self, x: usize) -> usize {
+        let root: usize = self.find(x);
+        self.size[root]
+    }
+
     pub(crate) fn all_sizes(&mut self) -> Vec<usize> {
         let mut sizes: Vec<usize> = Vec::new();
         let mut seen: Vec<bool> = vec![false; self.parent.len()];
@@ -6,6 +6,7 @@ use std::{
 
 mod point;
 
+use aoc::interval_set::IntervalSet;
 use point::Point;
 
 fn largest_area_part1(points: &[Point]) -> u64 {
@@ -15,40 +16,6 @@ fn largest_area_part1(points: &[Point]) -> u64 {
         .unwrap_or(0u64)
 }
 
-fn merge_intervals(mut intervals: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
-    if intervals.is_empty() {
-        return intervals;
-    }
-    intervals.sort_by_key(|(a, b): &(i64, i64)| (*a, *b));
-
-    let mut out: Vec<(i64, i64)> = Vec::with_capacity(intervals.len());
-    let mut cur: (i64, i64) = intervals[0];
-
-    for (a, b) in intervals.into_iter().skip(1) {
-        if a <= cur.1 + 1 {
-            cur.1 = cur.1.max(b);
-        } else {
-            out.push(cur);
-            cur = (a, b);
-        }
-    }
-    out.push(cur);
-    out
-}
-
-fn row_covers(intervals: &[(i64, i64)], x1: i64, x2: i64) -> bool {
-    // intervals are merged + sorted
-    for &(a, b) in intervals {
-        if a <= x1 && b >= x2 {
-            return true;
-        }
-        if a > x1 {
-            break;
-        }
-    }
-    false
-}
-
 fn largest_area_part2(points: &[Point]) -> u64 {
     if points.is_empty() {
         return 0;
@@ -102,7 +69,7 @@ fn largest_area_part2(points: &[Point]) -> u64 {
     }
 
     // Build merged valid intervals per row: (interior by parity) U (boundary)
-    let mut valid_by_y: HashMap<i64, Vec<(i64, i64)>> = HashMap::new();
+    let mut valid_by_y: HashMap<i64, IntervalSet<i64>> = HashMap::new();
     for y in min_y..=max_y {
         let mut intervals: Vec<(i64, i64)> = Vec::new();
 
@@ -118,7 +85,7 @@ fn largest_area_part2(points: &[Point]) -> u64 {
             intervals.extend_from_slice(b);
         }
 
-        let merged: Vec<(i64, i64)> = merge_intervals(intervals);
+        let merged: IntervalSet<i64> = intervals.into_iter().collect();
         if !merged.is_empty() {
             valid_by_y.insert(y, merged);
         }
@@ -140,7 +107,7 @@ fn largest_area_part2(points: &[Point]) -> u64 {
             let mut ok: bool = true;
             for y in y1..=y2 {
                 match valid_by_y.get(&y) {
-                    Some(intervals) if row_covers(intervals, x1, x2) => {}
+                    Some(intervals) if intervals.contains_range(x1, x2) => {}
                     _ => {
                         ok = false;
                         break;
@@ -0,0 +1,499 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Mul, Sub},
+};
+
+/// An arbitrary-precision unsigned integer stored as little-endian base-2³² limbs
+/// with no trailing zero limbs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub const fn zero() -> Self {
+        Self { limbs: Vec::new() }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Parses a non-empty string of ASCII digits.
+    pub fn from_decimal(digits: &str) -> Option<Self> {
+        if digits.is_empty() || !digits.bytes().all(|b: u8| b.is_ascii_digit()) {
+            return None;
+        }
+
+        let mut value: Self = Self::zero();
+        for chunk in digits.as_bytes().chunks(9) {
+            let chunk_value: u32 = chunk
+                .iter()
+                .fold(0, |acc: u32, &b: &u8| acc * 10 + u32::from(b - b'0'));
+            value.mul_add_small(10u32.pow(chunk.len() as u32), chunk_value);
+        }
+        Some(value)
+    }
+
+    fn normalize(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+        self
+    }
+
+    /// `self = self * factor + addend`
+    fn mul_add_small(&mut self, factor: u32, addend: u32) {
+        let mut carry: u64 = u64::from(addend);
+        for limb in &mut self.limbs {
+            let product: u64 = u64::from(*limb) * u64::from(factor) + carry;
+            *limb = product as u32;
+            carry = product >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+        *self = std::mem::take(self).normalize();
+    }
+
+    /// Divides in place by `divisor`, returning the remainder.
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder: u64 = 0;
+        for limb in self.limbs.iter_mut().rev() {
+            let current: u64 = (remainder << 32) | u64::from(*limb);
+            *limb = (current / u64::from(divisor)) as u32;
+            remainder = current % u64::from(divisor);
+        }
+        *self = std::mem::take(self).normalize();
+        remainder as u32
+    }
+
+    /// Number of significant bits, 0 for zero.
+    pub fn bit_len(&self) -> usize {
+        self.limbs.last().map_or(0, |&top: &u32| {
+            self.limbs.len() * 32 - top.leading_zeros() as usize
+        })
+    }
+
+    fn bit(&self, index: usize) -> bool {
+        self.limbs
+            .get(index / 32)
+            .is_some_and(|&limb: &u32| (limb >> (index % 32)) & 1 == 1)
+    }
+
+    fn shl1_or(&mut self, bit: bool) {
+        let mut carry: u32 = u32::from(bit);
+        for limb in &mut self.limbs {
+            let next: u32 = *limb >> 31;
+            *limb = (*limb << 1) | carry;
+            carry = next;
+        }
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+
+    /// `self - other`, or `None` if `other > self`.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if *self < *other {
+            return None;
+        }
+
+        let mut limbs: Vec<u32> = self.limbs.clone();
+        let mut borrow: i64 = 0;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut difference: i64 =
+                i64::from(*limb) - i64::from(other.limbs.get(i).copied().unwrap_or(0)) - borrow;
+            borrow = i64::from(difference < 0);
+            if difference < 0 {
+                difference += 1 << 32;
+            }
+            *limb = difference as u32;
+        }
+
+        Some(Self { limbs }.normalize())
+    }
+
+    /// Quotient and remainder, or `None` when dividing by zero.
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        if divisor.is_zero() {
+            return None;
+        }
+
+        if let [small] = divisor.limbs[..] {
+            let mut quotient: Self = self.clone();
+            let remainder: u32 = quotient.div_rem_small(small);
+            return Some((quotient, Self::from(u64::from(remainder))));
+        }
+
+        let mut quotient: Self = Self {
+            limbs: vec![0; self.limbs.len()],
+        };
+        let mut remainder: Self = Self::zero();
+
+        for index in (0..self.bit_len()).rev() {
+            remainder.shl1_or(self.bit(index));
+            if let Some(reduced) = remainder.checked_sub(divisor) {
+                remainder = reduced;
+                quotient.limbs[index / 32] |= 1 << (index % 32);
+            }
+        }
+
+        Some((quotient.normalize(), remainder))
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        }
+        .normalize()
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        Self {
+            limbs: (0..4)
+                .map(|limb: u32| (value >> (32 * limb)) as u32)
+                .collect(),
+        }
+        .normalize()
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+
+        let mut carry: u64 = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let sum: u64 =
+                u64::from(*limb) + u64::from(other.limbs.get(i).copied().unwrap_or(0)) + carry;
+            *limb = sum as u32;
+            carry = sum >> 32;
+        }
+        if carry > 0 {
+            self.limbs.push(carry as u32);
+        }
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut sum: BigUint = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        let mut limbs: Vec<u32> = vec![0; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry: u64 = 0;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let product: u64 = u64::from(a) * u64::from(b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad("0");
+        }
+
+        let mut chunks: Vec<u32> = Vec::new();
+        let mut rest: Self = self.clone();
+        while !rest.is_zero() {
+            chunks.push(rest.div_rem_small(1_000_000_000));
+        }
+
+        let mut digits: String = chunks
+            .pop()
+            .map(|top: u32| top.to_string())
+            .unwrap_or_default();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{chunk:09}"));
+        }
+        f.pad(&digits)
+    }
+}
+
+/// An arbitrary-precision signed integer. Division truncates toward zero and
+/// the remainder takes the sign of the dividend, matching Rust's primitives.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    negative: bool,
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    pub const fn zero() -> Self {
+        Self {
+            negative: false,
+            magnitude: BigUint::zero(),
+        }
+    }
+
+    fn new(negative: bool, magnitude: BigUint) -> Self {
+        Self {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn from_decimal(digits: &str) -> Option<Self> {
+        match digits.strip_prefix('-') {
+            Some(rest) => {
+                BigUint::from_decimal(rest).map(|magnitude: BigUint| Self::new(true, magnitude))
+            }
+            None => {
+                BigUint::from_decimal(digits).map(|magnitude: BigUint| Self::new(false, magnitude))
+            }
+        }
+    }
+
+    /// Truncating quotient and remainder, or `None` when dividing by zero.
+    pub fn div_rem(&self, divisor: &Self) -> Option<(Self, Self)> {
+        let (quotient, remainder) = self.magnitude.div_rem(&divisor.magnitude)?;
+        Some((
+            Self::new(self.negative != divisor.negative, quotient),
+            Self::new(self.negative, remainder),
+        ))
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::new(self.negative, &self.magnitude + &other.magnitude);
+        }
+
+        match self.magnitude.checked_sub(&other.magnitude) {
+            Some(difference) => BigInt::new(self.negative, difference),
+            None => BigInt::new(
+                other.negative,
+                other
+                    .magnitude
+                    .checked_sub(&self.magnitude)
+                    .expect("Larger magnitude minus smaller is non-negative"),
+            ),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &BigInt::new(!other.negative, other.magnitude.clone())
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::new(
+            self.negative != other.negative,
+            &self.magnitude * &other.magnitude,
+        )
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        Self::new(value < 0, BigUint::from(value.unsigned_abs()))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            f.pad(&format!("-{}", self.magnitude))
+        } else {
+            f.pad(&self.magnitude.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A small deterministic generator, so failures are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            self.0
+        }
+
+        /// A value with a random bit length, so that single-limb, multi-limb
+        /// and full-width operands all come up often.
+        fn value(&mut self) -> u128 {
+            let bits: u32 = (self.next() >> 32) as u32 % 129;
+            let raw: u128 = (u128::from(self.next()) << 64) | u128::from(self.next());
+            if bits == 0 { 0 } else { raw >> (128 - bits) }
+        }
+
+        fn signed(&mut self) -> i128 {
+            let value: i128 = self.value() as i128;
+            if self.next() >> 63 == 0 {
+                value
+            } else {
+                value.wrapping_neg()
+            }
+        }
+    }
+
+    fn unsigned(value: u128) -> BigUint {
+        BigUint::from(value)
+    }
+
+    fn signed(value: i128) -> BigInt {
+        BigInt::from_decimal(&value.to_string()).expect("Rust prints valid decimals")
+    }
+
+    #[test]
+    fn unsigned_operations_match_u128() {
+        let mut rng: Lcg = Lcg(37);
+        for _ in 0..100_000 {
+            let (a, b): (u128, u128) = (rng.value(), rng.value());
+            let (big_a, big_b): (BigUint, BigUint) = (unsigned(a), unsigned(b));
+
+            assert_eq!(big_a.to_string(), a.to_string());
+            assert_eq!(BigUint::from_decimal(&a.to_string()), Some(big_a.clone()));
+            assert_eq!(big_a.bit_len(), (128 - a.leading_zeros()) as usize);
+            assert_eq!(big_a.cmp(&big_b), a.cmp(&b));
+
+            let sum: BigUint = &big_a + &big_b;
+            if let Some(expected) = a.checked_add(b) {
+                assert_eq!(sum, unsigned(expected));
+            }
+            assert_eq!(sum.checked_sub(&big_b), Some(big_a.clone()));
+            assert_eq!(big_a.checked_sub(&big_b), a.checked_sub(b).map(unsigned));
+
+            let product: BigUint = &big_a * &big_b;
+            if let Some(expected) = a.checked_mul(b) {
+                assert_eq!(product, unsigned(expected));
+            }
+
+            match a.checked_div(b) {
+                Some(quotient) => {
+                    assert_eq!(
+                        big_a.div_rem(&big_b),
+                        Some((unsigned(quotient), unsigned(a % b)))
+                    );
+                    // Products beyond u128 divide back exactly.
+                    assert_eq!(product.div_rem(&big_b), Some((big_a, BigUint::zero())));
+                }
+                None => assert_eq!(big_a.div_rem(&big_b), None),
+            }
+        }
+    }
+
+    #[test]
+    fn signed_operations_match_i128() {
+        let mut rng: Lcg = Lcg(38);
+        for _ in 0..100_000 {
+            let (a, b): (i128, i128) = (rng.signed(), rng.signed());
+            let (big_a, big_b): (BigInt, BigInt) = (signed(a), signed(b));
+
+            assert_eq!(big_a.to_string(), a.to_string());
+            assert_eq!(big_a.cmp(&big_b), a.cmp(&b));
+
+            if let Some(expected) = a.checked_add(b) {
+                assert_eq!(&big_a + &big_b, signed(expected));
+            }
+            if let Some(expected) = a.checked_sub(b) {
+                assert_eq!(&big_a - &big_b, signed(expected));
+            }
+            assert_eq!(&(&big_a - &big_b) + &big_b, big_a);
+            if let Some(expected) = a.checked_mul(b) {
+                assert_eq!(&big_a * &big_b, signed(expected));
+            }
+
+            match (a.checked_div(b), a.checked_rem(b)) {
+                (Some(quotient), Some(remainder)) => assert_eq!(
+                    big_a.div_rem(&big_b),
+                    Some((signed(quotient), signed(remainder)))
+                ),
+                _ if b == 0 => assert_eq!(big_a.div_rem(&big_b), None),
+                // i128::MIN / -1 only overflows i128.
+                _ => assert_eq!(
+                    big_a.div_rem(&big_b),
+                    Some((&BigInt::zero() - &big_a, BigInt::zero()))
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn zero_is_never_negative() {
+        let five: BigInt = BigInt::from(5);
+        assert_eq!(&five - &five, BigInt::zero());
+        assert_eq!(&BigInt::from(-5) * &BigInt::zero(), BigInt::zero());
+        assert_eq!(
+            BigInt::from(-4).div_rem(&BigInt::from(2)),
+            Some((BigInt::from(-2), BigInt::zero()))
+        );
+        assert_eq!(BigInt::from_decimal("-0"), Some(BigInt::zero()));
+        assert_eq!(BigInt::zero().to_string(), "0");
+    }
+}
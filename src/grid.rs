@@ -0,0 +1,299 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// The four orthogonal directions as `(dx, dy)` offsets.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(1, 0), (0, -1), (-1, 0), (0, 1)];
+
+/// The eight orthogonal and diagonal directions as `(dx, dy)` offsets.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves by `(dx, dy)`, returning `None` if either component would become negative.
+    pub const fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        match (self.x.checked_add_signed(dx), self.y.checked_add_signed(dy)) {
+            (Some(x), Some(y)) => Some(Self { x, y }),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    RaggedRow {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RaggedRow {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {row} has {found} cells, expected {expected} like the first row"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+/// A rectangular grid stored row-major in a single contiguous buffer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from rows that must all have the same length.
+    pub fn from_rows<I, R>(rows: I) -> Result<Self, GridError>
+    where
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells: Vec<T> = Vec::new();
+        let mut width: usize = 0;
+        let mut height: usize = 0;
+
+        for row in rows {
+            let before: usize = cells.len();
+            cells.extend(row);
+            let found: usize = cells.len() - before;
+
+            if height == 0 {
+                width = found;
+            } else if found != width {
+                return Err(GridError::RaggedRow {
+                    row: height,
+                    expected: width,
+                    found,
+                });
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Builds a grid from rows of any length, padding short rows with `fill`.
+    pub fn from_rows_padded<I, R>(rows: I, fill: T) -> Self
+    where
+        T: Clone,
+        I: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let rows: Vec<Vec<T>> = rows
+            .into_iter()
+            .map(|row: R| row.into_iter().collect())
+            .collect();
+        let width: usize = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height: usize = rows.len();
+        let mut cells: Vec<T> = Vec::with_capacity(width * height);
+
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub const fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        if self.contains(coord) {
+            Some(&self.cells[coord.y * self.width + coord.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        if self.contains(coord) {
+            Some(&mut self.cells[coord.y * self.width + coord.x])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `coord` by `(dx, dy)`, returning `None` if the result leaves the grid.
+    pub fn step(&self, coord: Coord, dx: isize, dy: isize) -> Option<Coord> {
+        coord
+            .offset(dx, dy)
+            .filter(|&next: &Coord| self.contains(next))
+    }
+
+    /// Moves `coord` by `(dx, dy)`, wrapping around the edges like a torus.
+    /// Returns `None` only if the grid is empty.
+    pub fn step_wrapping(&self, coord: Coord, dx: isize, dy: isize) -> Option<Coord> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let x: isize = (coord.x as isize + dx).rem_euclid(self.width as isize);
+        let y: isize = (coord.y as isize + dy).rem_euclid(self.height as isize);
+        Some(Coord::new(x as usize, y as usize))
+    }
+
+    /// In-bounds neighbors of `coord` for an arbitrary set of offsets.
+    pub fn neighbors<'a>(
+        &'a self,
+        coord: Coord,
+        directions: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Coord> + 'a {
+        directions
+            .iter()
+            .filter_map(move |&(dx, dy)| self.step(coord, dx, dy))
+    }
+
+    /// In-bounds orthogonal (4-connected) neighbors of `coord`.
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbors(coord, &ORTHOGONAL)
+    }
+
+    /// In-bounds orthogonal and diagonal (8-connected) neighbors of `coord`.
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        self.neighbors(coord, &ALL_DIRECTIONS)
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y: usize| self.row(y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// All coordinates in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> + use<T> {
+        let width: usize = self.width;
+        (0..self.height).flat_map(move |y: usize| (0..width).map(move |x: usize| Coord::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord} is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord} is outside the {width}x{height} grid"))
+    }
+}
+
+impl FromStr for Grid<u8> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_rows(s.lines().map(|line: &str| line.bytes()))
+    }
+}
+
+impl fmt::Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(f, "{}", String::from_utf8_lossy(row))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,403 @@
+use std::fmt;
+
+/// Integer types usable as interval endpoints.
+pub trait Endpoint: Copy + Ord + fmt::Debug {
+    fn checked_next(self) -> Option<Self>;
+    fn checked_prev(self) -> Option<Self>;
+    /// Number of values in `start..=end`, assuming `start <= end`.
+    fn count_between(start: Self, end: Self) -> u128;
+}
+
+macro_rules! impl_endpoint {
+    ($($t:ty),*) => {$(
+        impl Endpoint for $t {
+            fn checked_next(self) -> Option<Self> {
+                self.checked_add(1)
+            }
+
+            fn checked_prev(self) -> Option<Self> {
+                self.checked_sub(1)
+            }
+
+            fn count_between(start: Self, end: Self) -> u128 {
+                (end as i128 - start as i128) as u128 + 1
+            }
+        }
+    )*};
+}
+
+impl_endpoint!(u32, u64, usize, i32, i64);
+
+/// Whether a range ending at `end` overlaps or touches one starting at `start`.
+fn reaches<T: Endpoint>(end: T, start: T) -> bool {
+    end >= start || end.checked_next() == Some(start)
+}
+
+/// A set of integers stored as sorted, disjoint, non-adjacent inclusive ranges.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Endpoint> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Endpoint> IntervalSet<T> {
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// The merged ranges in ascending order.
+    pub fn ranges(&self) -> &[(T, T)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Total number of integers in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| T::count_between(start, end))
+            .sum()
+    }
+
+    /// Adds `start..=end`, merging it with any overlapping or adjacent range.
+    /// Does nothing if `start > end`.
+    pub fn insert(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        let lo: usize = self
+            .ranges
+            .partition_point(|&(_, b): &(T, T)| !reaches(b, start));
+        let hi: usize = self
+            .ranges
+            .partition_point(|&(a, _): &(T, T)| reaches(end, a));
+
+        let merged: (T, T) = if lo < hi {
+            (start.min(self.ranges[lo].0), end.max(self.ranges[hi - 1].1))
+        } else {
+            (start, end)
+        };
+
+        self.ranges.splice(lo..hi, [merged]);
+    }
+
+    /// Removes `start..=end`, splitting ranges that straddle either end.
+    /// Does nothing if `start > end`.
+    pub fn remove(&mut self, start: T, end: T) {
+        if start > end {
+            return;
+        }
+
+        let lo: usize = self.ranges.partition_point(|&(_, b): &(T, T)| b < start);
+        let hi: usize = self.ranges.partition_point(|&(a, _): &(T, T)| a <= end);
+
+        if lo >= hi {
+            return;
+        }
+
+        let mut pieces: Vec<(T, T)> = Vec::with_capacity(2);
+        let (first_start, _) = self.ranges[lo];
+        let (_, last_end) = self.ranges[hi - 1];
+
+        if let Some(before) = start.checked_prev().filter(|_| first_start < start) {
+            pieces.push((first_start, before));
+        }
+        if let Some(after) = end.checked_next().filter(|_| last_end > end) {
+            pieces.push((after, last_end));
+        }
+
+        self.ranges.splice(lo..hi, pieces);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.find(value).is_some()
+    }
+
+    /// Index into [`ranges`](Self::ranges) of the range holding `value`, found by binary search.
+    pub fn find(&self, value: T) -> Option<usize> {
+        let index: usize = self.ranges.partition_point(|&(_, b): &(T, T)| b < value);
+        self.ranges
+            .get(index)
+            .filter(|&&(a, _)| a <= value)
+            .map(|_| index)
+    }
+
+    /// [`find`](Self::find) for every value, answered with a single sweep over
+    /// the values in sorted order. Results are in the order of `values`.
+    pub fn find_all(&self, values: &[T]) -> Vec<Option<usize>> {
+        let mut order: Vec<usize> = (0..values.len()).collect();
+        order.sort_unstable_by_key(|&i: &usize| values[i]);
+
+        let mut found: Vec<Option<usize>> = vec![None; values.len()];
+        let mut range: usize = 0;
+
+        for i in order {
+            let value: T = values[i];
+            while range < self.ranges.len() && self.ranges[range].1 < value {
+                range += 1;
+            }
+            if range == self.ranges.len() {
+                break;
+            }
+            if self.ranges[range].0 <= value {
+                found[i] = Some(range);
+            }
+        }
+
+        found
+    }
+
+    /// Whether every integer in `start..=end` is in the set.
+    pub fn contains_range(&self, start: T, end: T) -> bool {
+        let index: usize = self.ranges.partition_point(|&(_, b): &(T, T)| b < start);
+        self.ranges
+            .get(index)
+            .is_some_and(|&(a, b): &(T, T)| a <= start && end <= b)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result: Self = self.clone();
+        for &(start, end) in &other.ranges {
+            result.insert(start, end);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges: Vec<(T, T)> = Vec::new();
+        let (mut i, mut j): (usize, usize) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a1, b1) = self.ranges[i];
+            let (a2, b2) = other.ranges[j];
+            let start: T = a1.max(a2);
+            let end: T = b1.min(b2);
+
+            if start <= end {
+                ranges.push((start, end));
+            }
+
+            if b1 < b2 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result: Self = self.clone();
+        for &(start, end) in &other.ranges {
+            result.remove(start, end);
+        }
+        result
+    }
+
+    /// Every integer in `lo..=hi` that is not in the set.
+    pub fn complement(&self, lo: T, hi: T) -> Self {
+        let mut bounds: Self = Self::new();
+        bounds.insert(lo, hi);
+        bounds.difference(self)
+    }
+}
+
+impl<T: Endpoint> FromIterator<(T, T)> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        let mut ranges: Vec<(T, T)> = iter
+            .into_iter()
+            .filter(|&(start, end): &(T, T)| start <= end)
+            .collect();
+        ranges.sort_unstable();
+
+        let mut merged: Vec<(T, T)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if reaches(last.1, start) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// A small deterministic generator, so failures are reproducible.
+    struct Lcg(u64);
+
+    impl Lcg {
+        fn below(&mut self, bound: usize) -> usize {
+            self.0 = self
+                .0
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            ((self.0 >> 33) % bound as u64) as usize
+        }
+    }
+
+    /// Runs of consecutive values at the bottom, middle and top of `T`'s range.
+    /// Ranges are drawn from within one run, so the naive model stays small
+    /// while `T::MIN` and `T::MAX` still show up as endpoints.
+    fn clusters<T: Endpoint>(min: T, middle: T, max: T) -> Vec<Vec<T>> {
+        let run = |first: T| -> Vec<T> {
+            std::iter::successors(Some(first), |&value: &T| value.checked_next())
+                .take(6)
+                .collect()
+        };
+        let top: T = (0..5).fold(max, |value: T, _| value.checked_prev().unwrap());
+        vec![run(min), run(middle), run(top)]
+    }
+
+    fn random_range<T: Endpoint>(rng: &mut Lcg, clusters: &[Vec<T>]) -> (T, T) {
+        let cluster: &Vec<T> = &clusters[rng.below(clusters.len())];
+        let a: T = cluster[rng.below(cluster.len())];
+        let b: T = cluster[rng.below(cluster.len())];
+        (a.min(b), a.max(b))
+    }
+
+    fn values<T: Endpoint>(start: T, end: T) -> BTreeSet<T> {
+        std::iter::successors(Some(start), |&value: &T| {
+            value.checked_next().filter(|&next: &T| next <= end)
+        })
+        .collect()
+    }
+
+    fn model<T: Endpoint>(set: &IntervalSet<T>) -> BTreeSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|&(start, end)| values(start, end))
+            .collect()
+    }
+
+    fn assert_canonical<T: Endpoint>(set: &IntervalSet<T>) {
+        for &(start, end) in set.ranges() {
+            assert!(start <= end, "inverted range in {set:?}");
+        }
+        for pair in set.ranges().windows(2) {
+            assert!(
+                !reaches(pair[0].1, pair[1].0),
+                "overlapping or adjacent ranges in {set:?}"
+            );
+        }
+    }
+
+    fn random_set<T: Endpoint>(
+        rng: &mut Lcg,
+        clusters: &[Vec<T>],
+    ) -> (IntervalSet<T>, BTreeSet<T>) {
+        let mut set: IntervalSet<T> = IntervalSet::new();
+        let mut expected: BTreeSet<T> = BTreeSet::new();
+
+        for _ in 0..rng.below(6) {
+            let (start, end) = random_range(rng, clusters);
+            if rng.below(3) == 0 {
+                set.remove(start, end);
+                expected.retain(|value: &T| *value < start || *value > end);
+            } else {
+                set.insert(start, end);
+                expected.extend(values(start, end));
+            }
+            assert_canonical(&set);
+            assert_eq!(model(&set), expected);
+        }
+
+        (set, expected)
+    }
+
+    fn check_against_model<T: Endpoint>(clusters: &[Vec<T>]) {
+        let mut rng: Lcg = Lcg(42);
+        let all: Vec<T> = clusters.concat();
+
+        for _ in 0..500 {
+            let (a, model_a) = random_set(&mut rng, clusters);
+            let (b, model_b) = random_set(&mut rng, clusters);
+
+            assert_eq!(a.len(), model_a.len() as u128);
+            assert_eq!(a.is_empty(), model_a.is_empty());
+
+            for &value in &all {
+                assert_eq!(a.contains(value), model_a.contains(&value));
+                if let Some(index) = a.find(value) {
+                    let (start, end) = a.ranges()[index];
+                    assert!(start <= value && value <= end);
+                }
+            }
+
+            let probes: Vec<T> = (0..8).map(|_| all[rng.below(all.len())]).collect();
+            let expected: Vec<Option<usize>> =
+                probes.iter().map(|&value: &T| a.find(value)).collect();
+            assert_eq!(a.find_all(&probes), expected);
+
+            let (start, end) = random_range(&mut rng, clusters);
+            assert_eq!(
+                a.contains_range(start, end),
+                values(start, end).is_subset(&model_a)
+            );
+
+            let union: IntervalSet<T> = a.union(&b);
+            let intersection: IntervalSet<T> = a.intersection(&b);
+            let difference: IntervalSet<T> = a.difference(&b);
+            let complement: IntervalSet<T> = a.complement(start, end);
+
+            for set in [&union, &intersection, &difference, &complement] {
+                assert_canonical(set);
+            }
+            assert_eq!(model(&union), &model_a | &model_b);
+            assert_eq!(model(&intersection), &model_a & &model_b);
+            assert_eq!(model(&difference), &model_a - &model_b);
+            assert_eq!(model(&complement), &values(start, end) - &model_a);
+
+            let collected: IntervalSet<T> = a.ranges().iter().chain(b.ranges()).copied().collect();
+            assert_eq!(collected, union);
+        }
+    }
+
+    #[test]
+    fn signed_operations_match_model() {
+        check_against_model(&clusters(i32::MIN, -3, i32::MAX));
+    }
+
+    #[test]
+    fn unsigned_operations_match_model() {
+        check_against_model(&clusters(u64::MIN, u64::MAX / 2, u64::MAX));
+    }
+
+    #[test]
+    fn full_range_operations() {
+        let mut set: IntervalSet<i64> = IntervalSet::new();
+        set.insert(i64::MIN, i64::MAX);
+        assert_eq!(set.len(), 1u128 << 64);
+        assert!(set.contains_range(i64::MIN, i64::MAX));
+
+        set.remove(i64::MIN, i64::MIN);
+        set.remove(i64::MAX, i64::MAX);
+        assert_eq!(set.ranges(), &[(i64::MIN + 1, i64::MAX - 1)]);
+
+        let complement: IntervalSet<i64> = set.complement(i64::MIN, i64::MAX);
+        assert_eq!(
+            complement.ranges(),
+            &[(i64::MIN, i64::MIN), (i64::MAX, i64::MAX)]
+        );
+
+        set.insert(i64::MAX, i64::MAX);
+        set.insert(i64::MIN, i64::MIN);
+        assert_eq!(set.ranges(), &[(i64::MIN, i64::MAX)]);
+        assert!(set.complement(i64::MIN, i64::MAX).is_empty());
+    }
+}
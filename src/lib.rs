@@ -0,0 +1,4 @@
+pub mod bigint;
+pub mod grid;
+pub mod interval_set;
+pub mod render;
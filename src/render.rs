@@ -0,0 +1,77 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use crate::grid::Grid;
+
+pub type Rgb = [u8; 3];
+
+/// Writes `pixels` as a binary PPM (P6) image, drawing each cell as a
+/// `scale`×`scale` block.
+pub fn write_ppm<W: Write>(out: &mut W, pixels: &Grid<Rgb>, scale: usize) -> io::Result<()> {
+    let scale: usize = scale.max(1);
+    writeln!(
+        out,
+        "P6\n{} {}\n255",
+        pixels.width() * scale,
+        pixels.height() * scale
+    )?;
+
+    let mut line: Vec<u8> = Vec::with_capacity(pixels.width() * scale * 3);
+    for row in pixels.rows() {
+        line.clear();
+        for pixel in row {
+            for _ in 0..scale {
+                line.extend_from_slice(pixel);
+            }
+        }
+        for _ in 0..scale {
+            out.write_all(&line)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes `pixels` to a new PPM file at `path`; see [`write_ppm`].
+pub fn save_ppm(path: &Path, pixels: &Grid<Rgb>, scale: usize) -> io::Result<()> {
+    let mut out: BufWriter<File> = BufWriter::new(File::create(path)?);
+    write_ppm(&mut out, pixels, scale)?;
+    out.flush()
+}
+
+/// Writes `glyphs` to a terminal, coloring each cell with a 24-bit ANSI
+/// foreground color. Cells without a color use the terminal default.
+pub fn write_ansi<W: Write>(
+    out: &mut W,
+    glyphs: &Grid<u8>,
+    colors: &Grid<Option<Rgb>>,
+) -> io::Result<()> {
+    for y in 0..glyphs.height() {
+        for (&glyph, &color) in glyphs.row(y).iter().zip(colors.row(y)) {
+            match color {
+                Some([r, g, b]) => write!(out, "\x1b[38;2;{r};{g};{b}m{}", glyph as char)?,
+                None => write!(out, "\x1b[0m{}", glyph as char)?,
+            }
+        }
+        writeln!(out, "\x1b[0m")?;
+    }
+
+    Ok(())
+}
+
+/// Maps `t` in `0.0..=1.0` onto a red → yellow → blue heat gradient.
+pub fn heat(t: f64) -> Rgb {
+    let t: f64 = t.clamp(0.0, 1.0);
+    let lerp = |a: u8, b: u8, t: f64| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+
+    if t < 0.5 {
+        let t: f64 = t * 2.0;
+        [lerp(220, 240, t), lerp(40, 200, t), lerp(30, 40, t)]
+    } else {
+        let t: f64 = (t - 0.5) * 2.0;
+        [lerp(240, 40, t), lerp(200, 90, t), lerp(40, 220, t)]
+    }
+}